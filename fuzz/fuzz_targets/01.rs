@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[allow(dead_code)]
+mod solution {
+    include!("../../src/bin/01.rs");
+}
+
+fuzz_target!(|data: &[u8]| {
+    let _ = solution::part_one(data);
+    let _ = solution::part_two(data);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[allow(dead_code)]
+mod solution {
+    include!("../../src/bin/05.rs");
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = solution::part_one(input);
+        let _ = solution::part_two(input);
+    }
+});
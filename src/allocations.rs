@@ -0,0 +1,164 @@
+/// Counting of heap allocations, to see what a solution allocates besides how long it takes.
+///
+/// With the `count_allocations` feature, [`CountingAllocator`] is installed as the global
+/// allocator. It forwards to the system allocator and keeps track of the number of allocations as
+/// well as of the live and peak heap bytes. Without the feature, [`measure`] reports nothing.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator that counts allocations on top of [`System`].
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn grow(size: usize) {
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        CURRENT_BYTES.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation is counted as an allocation, growing a `Vec` is not free.
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            Self::grow(new_size);
+            Self::shrink(layout.size());
+        }
+        new_ptr
+    }
+}
+
+#[cfg(feature = "count_allocations")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Whether allocations are counted, i.e. the `count_allocations` feature is enabled.
+pub fn enabled() -> bool {
+    cfg!(feature = "count_allocations")
+}
+
+/// Heap usage of a measured closure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Stats {
+    /// Number of allocations and reallocations.
+    pub allocations: u64,
+    /// Highest number of heap bytes in use at once, on top of what was in use before.
+    pub peak_bytes: usize,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} peak",
+            self.allocations,
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Runs `f` and returns its heap usage, or [`None`] if allocations are not counted.
+/// Measurements are process-wide: allocations of other threads running meanwhile are included.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let baseline = CURRENT_BYTES.load(Ordering::Relaxed);
+    PEAK_BYTES.store(baseline, Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = Stats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline),
+    };
+    (result, Some(stats))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, Stats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn displays_stats() {
+        let stats = Stats {
+            allocations: 12,
+            peak_bytes: 2048,
+        };
+        assert_eq!(stats.to_string(), "12 allocs, 2.0 KiB peak");
+    }
+
+    #[cfg(feature = "count_allocations")]
+    #[test]
+    fn counts_allocations() {
+        let (_, stats) = super::measure(|| {
+            let a = vec![0_u8; 4096];
+            let b = vec![0_u8; 1024];
+            drop(a);
+            b
+        });
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 2);
+        assert!(stats.peak_bytes >= 5120);
+    }
+}
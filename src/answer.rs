@@ -0,0 +1,169 @@
+/// The result of a solution part, whatever shape the puzzle asks for.
+///
+/// Parts return `Option<T>` for any `T` that converts into an [`Answer`]: integers of every width,
+/// [`BigInt`] and [`BigUint`], strings, or an [`Answer`] built directly, e.g. with
+/// [`Answer::letters`] for puzzles whose answer is drawn in block letters.
+use std::fmt::Display;
+
+use num::{BigInt, BigUint, ToPrimitive};
+
+use crate::grid::Grid;
+use crate::ocr;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    /// An integer that does not fit into an [`i128`].
+    BigInteger(BigInt),
+    /// Text, e.g. a password or several values joined by commas.
+    Text(String),
+    /// A picture of block letters, see [`ocr`].
+    Letters(String),
+}
+
+impl Answer {
+    /// A picture of block letters with `#` or `█` for lit pixels, read with [`ocr::read`].
+    pub fn letters(picture: impl Into<String>) -> Self {
+        Answer::Letters(picture.into())
+    }
+
+    /// Block letters drawn on a grid of pixels.
+    pub fn from_pixels(grid: &Grid<bool>) -> Self {
+        Answer::Letters(ocr::render(grid))
+    }
+
+    /// The text that is submitted and compared with recorded answers, [`None`] if the answer
+    /// cannot be written on a single line, e.g. unknown letters.
+    pub fn text(&self) -> Option<String> {
+        match self {
+            Answer::Letters(picture) => ocr::read(picture),
+            Answer::Text(text) if text.contains('\n') => None,
+            answer => Some(answer.to_string()),
+        }
+    }
+
+    /// Whether the answer is printed below its part instead of next to it.
+    pub fn is_multiline(&self) -> bool {
+        self.to_string().contains('\n')
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::BigInteger(n) => write!(f, "{n}"),
+            Answer::Text(text) | Answer::Letters(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Answer::Integer(n),
+            Err(_) => Answer::BigInteger(n.into()),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    /// Keeps integers that fit into an [`i128`] as [`Answer::Integer`], so answers compare equal
+    /// whichever type computed them.
+    fn from(n: BigInt) -> Self {
+        match n.to_i128() {
+            Some(n) => Answer::Integer(n),
+            None => Answer::BigInteger(n),
+        }
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        BigInt::from(n).into()
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(c: char) -> Self {
+        Answer::Text(c.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use num::{BigInt, BigUint};
+
+    use super::Answer;
+
+    #[test]
+    fn converts_integers() {
+        assert_eq!(Answer::from(42_u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-3_i64), Answer::Integer(-3));
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from(BigInt::from(7)), Answer::Integer(7));
+
+        let big = BigUint::from(u128::MAX) * 3_u32;
+        assert_eq!(
+            Answer::from(big.clone()),
+            Answer::BigInteger(BigInt::from(big.clone()))
+        );
+        assert_eq!(Answer::from(big.clone()).text(), Some(big.to_string()));
+        assert!(matches!(Answer::from(u128::MAX), Answer::BigInteger(_)));
+    }
+
+    #[test]
+    fn reads_text() {
+        assert_eq!(Answer::from("4,6,3").text(), Some("4,6,3".into()));
+        assert_eq!(Answer::from("a\nb").text(), None);
+        assert!(Answer::from("a\nb").is_multiline());
+        assert!(!Answer::from(1_u8).is_multiline());
+
+        let picture = "#..#.###.\n#..#..#..\n####..#..\n#..#..#..\n#..#..#..\n#..#.###.";
+        let letters = Answer::letters(picture);
+        assert_eq!(letters.text(), Some("HI".into()));
+        assert!(letters.is_multiline());
+        assert_eq!(letters.to_string(), picture);
+        assert_eq!(Answer::letters("#").text(), None);
+    }
+}
@@ -1,4 +1,6 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, mmap);
+
+use advent_of_code::matcher::Matcher;
 
 fn compute_result(first_digit: Option<u32>, last_digit: Option<u32>) -> Option<u32> {
     match (first_digit, last_digit) {
@@ -7,65 +9,40 @@ fn compute_result(first_digit: Option<u32>, last_digit: Option<u32>) -> Option<u
     }
 }
 
-fn extract_u32_from_str(s: &str) -> Option<u32> {
-    let mut first_digit = None;
-    let mut last_digit = None;
+fn lines(input: &[u8]) -> impl Iterator<Item = &[u8]> {
+    input
+        .split(|&b| b == b'\n')
+        .filter(|line| !line.is_empty() && *line != b"\r")
+}
 
-    for c in s.chars() {
-        if let Some(c) = c.to_digit(10) {
-            if first_digit.is_none() {
-                first_digit = Some(c);
-            }
-            last_digit = Some(c);
-        }
-    }
+fn extract_u32_from_line(line: &[u8]) -> Option<u32> {
+    let mut digits = line.iter().filter(|b| b.is_ascii_digit());
+    let first_digit = digits.next().map(|b| u32::from(b - b'0'));
+    let last_digit = digits.next_back().map(|b| u32::from(b - b'0')).or(first_digit);
 
     compute_result(first_digit, last_digit)
 }
 
-static NUMBERS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+static NUMBERS: [&str; 18] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five", "six",
+    "seven", "eight", "nine",
 ];
 
-fn extract_u32_with_literal_from_slice(s: &str) -> Option<u32> {
-    if let Some(d) = s.chars().next().and_then(|c| c.to_digit(10)) {
-        return Some(d);
-    }
-
-    for (i, n) in NUMBERS.iter().enumerate() {
-        if s.starts_with(n) {
-            return Some(i as u32 + 1);
-        }
-    }
-
-    None
-}
-
-fn extract_u32_with_literal_from_str(mut s: &str) -> Option<u32> {
-    let mut first_digit = None;
-    let mut last_digit = None;
-
-    while !s.is_empty() {
-        let d = extract_u32_with_literal_from_slice(s);
-        s = &s[1..];
-
-        if let Some(d) = d {
-            if first_digit.is_none() {
-                first_digit = Some(d);
-            }
-            last_digit = Some(d);
-        }
-    }
-
-    compute_result(first_digit, last_digit)
+fn extract_u32_with_literal_from_line(matcher: &Matcher, line: &[u8]) -> Option<u32> {
+    let (first, last) = matcher.find_first_and_last(line)?;
+    let digit = |pattern: usize| pattern as u32 % 9 + 1;
+    compute_result(Some(digit(first.pattern)), Some(digit(last.pattern)))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    input.lines().map(extract_u32_from_str).sum()
+pub fn part_one(input: &[u8]) -> Option<u32> {
+    lines(input).map(extract_u32_from_line).sum()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    input.lines().map(extract_u32_with_literal_from_str).sum()
+pub fn part_two(input: &[u8]) -> Option<u32> {
+    let matcher = Matcher::new(NUMBERS);
+    lines(input)
+        .map(|line| extract_u32_with_literal_from_line(&matcher, line))
+        .sum()
 }
 
 #[cfg(test)]
@@ -73,17 +50,21 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_extract_u32_from_str() {
-        assert_eq!(extract_u32_from_str("1abc2"), Some(12));
-        assert_eq!(extract_u32_from_str("a1b2c3d4e5f"), Some(15));
-        assert_eq!(extract_u32_from_str("treb7uchet"), Some(77));
+    fn test_extract_u32_from_line() {
+        assert_eq!(extract_u32_from_line(b"1abc2"), Some(12));
+        assert_eq!(extract_u32_from_line(b"a1b2c3d4e5f"), Some(15));
+        assert_eq!(extract_u32_from_line(b"treb7uchet"), Some(77));
     }
 
     #[test]
-    fn test_extract_u32_with_literal_from_str() {
-        assert_eq!(extract_u32_with_literal_from_str("two1nine"), Some(29));
-        assert_eq!(extract_u32_with_literal_from_str("eightwothree"), Some(83));
-        assert_eq!(extract_u32_with_literal_from_str("zoneight234"), Some(14));
+    fn test_extract_u32_with_literal_from_line() {
+        let matcher = Matcher::new(NUMBERS);
+        let extract = |s: &str| extract_u32_with_literal_from_line(&matcher, s.as_bytes());
+        assert_eq!(extract("two1nine"), Some(29));
+        assert_eq!(extract("eightwothree"), Some(83));
+        assert_eq!(extract("zoneight234"), Some(14));
+        assert_eq!(extract("oneight"), Some(18));
+        assert_eq!(extract("ünf3→sevené"), Some(37));
     }
 
     #[test]
@@ -92,7 +73,14 @@ mod tests {
 pqr3stu8vwx
 a1b2c3d4e5f
 treb7uchet";
-        let result = part_one(example);
+        let result = part_one(example.as_bytes());
+        assert_eq!(result, Some(142));
+    }
+
+    #[test]
+    fn test_part_one_crlf() {
+        let example = "1abc2\r\npqr3stu8vwx\r\na1b2c3d4e5f\r\ntreb7uchet\r\n";
+        let result = part_one(example.as_bytes());
         assert_eq!(result, Some(142));
     }
 
@@ -106,7 +94,7 @@ xtwone3four
 zoneight234
 7pqrstsixteen
 ";
-        let result = part_two(example);
+        let result = part_two(example.as_bytes());
         assert_eq!(result, Some(281));
     }
 }
@@ -2,13 +2,11 @@ advent_of_code::solution!(3);
 
 use std::collections::HashMap;
 
+use advent_of_code::grid::Grid;
+use advent_of_code::viz::{self, Color, Image};
 use anyhow::{Context, Result};
 
-struct Map {
-    map: Vec<Vec<char>>,
-    width: usize,
-    height: usize,
-}
+type Map = Grid<char>;
 
 struct Number {
     value: u32,
@@ -18,24 +16,16 @@ struct Number {
 }
 
 fn parse_map(input: &str) -> Result<Map> {
-    let map = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let width = map.first().context("Missing first row")?.len();
-    let height = map.len();
-
-    Ok(Map { map, width, height })
+    Grid::parse(input).context("Map should be a non-empty rectangle")
 }
 
 fn find_numbers(map: &Map) -> Vec<Number> {
     let mut numbers = Vec::new();
 
-    for y in 0..map.height {
+    for y in 0..map.height() {
         let mut current_number: Option<Number> = None;
-        for x in 0..map.width {
-            match map.map[y][x].to_digit(10) {
+        for x in 0..map.width() {
+            match map[(x, y)].to_digit(10) {
                 Some(d) => match current_number.as_mut() {
                     Some(number) => {
                         number.len += 1;
@@ -86,7 +76,7 @@ fn get_adjacent_symbols(map: &Map, number: &Number) -> Vec<(usize, usize)> {
 
     // Scan top and bottom line
     for current_x in first_x..=x + len {
-        if current_x >= map.width {
+        if current_x >= map.width() {
             continue;
         }
         let mut y_to_check = vec![y + 1];
@@ -95,10 +85,10 @@ fn get_adjacent_symbols(map: &Map, number: &Number) -> Vec<(usize, usize)> {
         }
 
         for current_y in y_to_check {
-            if current_y >= map.height {
+            if current_y >= map.height() {
                 continue;
             }
-            if is_symbol(map.map[current_y][current_x]) {
+            if is_symbol(map[(current_x, current_y)]) {
                 symbols.push((current_x, current_y));
             }
         }
@@ -111,10 +101,10 @@ fn get_adjacent_symbols(map: &Map, number: &Number) -> Vec<(usize, usize)> {
     }
 
     for current_x in x_to_check {
-        if current_x >= map.width {
+        if current_x >= map.width() {
             continue;
         }
-        if is_symbol(map.map[y][current_x]) {
+        if is_symbol(map[(current_x, y)]) {
             symbols.push((current_x, y));
         }
     }
@@ -134,9 +124,41 @@ pub fn part_one(input: &str) -> Option<u32> {
     let numbers = find_numbers(&map);
     let numbers = find_numbers_with_adjacent_symbol(&map, &numbers);
 
+    if viz::enabled() {
+        visualize_part_numbers(&map, &numbers);
+    }
+
     numbers.iter().map(|n| n.value).sum::<u32>().into()
 }
 
+/// Shows part numbers in green, other numbers in red and symbols in yellow.
+fn visualize_part_numbers(map: &Map, part_numbers: &[&Number]) {
+    let is_part_number = |(x, y): (usize, usize)| {
+        part_numbers
+            .iter()
+            .any(|n| n.y == y && (n.x..n.x + n.len).contains(&x))
+    };
+    let color = |position, c: &char| match c {
+        '.' => None,
+        c if c.is_ascii_digit() && is_part_number(position) => Some(Color::GREEN),
+        c if c.is_ascii_digit() => Some(Color::RED),
+        _ => Some(Color::YELLOW),
+    };
+
+    println!("{}", viz::render_ansi(map, color));
+
+    let image = Image::from_grid(map, 4, |position, c| {
+        color(position, c).unwrap_or(Color::BLACK)
+    });
+    match viz::output_path(DAY, "part-numbers", "png").and_then(|path| {
+        image.write_png(&path)?;
+        Ok(path)
+    }) {
+        Ok(path) => println!("Wrote {}", path.display()),
+        Err(e) => eprintln!("Failed to write visualisation: {e}"),
+    }
+}
+
 fn find_gears_adjacent_numbers<'a>(
     map: &Map,
     numbers: &'a [Number],
@@ -146,7 +168,7 @@ fn find_gears_adjacent_numbers<'a>(
     for number in numbers {
         let adjacent_symbols = get_adjacent_symbols(map, number);
         for (x, y) in adjacent_symbols {
-            let c = map.map[y][x];
+            let c = map[(x, y)];
             if c != '*' {
                 continue;
             }
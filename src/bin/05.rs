@@ -127,10 +127,9 @@ impl Almanac {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<u64> {
     let almanac = Almanac::new_from_str(input).unwrap();
-    let closest_location = almanac.get_closest_seed_location()?;
-    Some(closest_location as u32)
+    almanac.get_closest_seed_location()
 }
 
 fn split_range(range: &Range, source: u64) -> (Option<Range>, Option<Range>) {
@@ -217,7 +216,7 @@ fn map_range_recursive(range: &Range, mappings: &[Vec<Mapping>]) -> Vec<Range> {
     }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<u64> {
     let almanac = Almanac::new_from_str(input).unwrap();
 
     // seeds are now ranges
@@ -238,7 +237,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     seeds
         .iter()
         .flat_map(|range| map_range_recursive(range, &almanac.mappings))
-        .map(|range| range.start as u32)
+        .map(|range| range.start)
         .min()
 }
 
@@ -292,6 +291,79 @@ humidity-to-location map:
         assert_eq!(result, Some(46));
     }
 
+    static MAP_NAMES: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+
+    type Case = (Vec<(u64, u64)>, Vec<Vec<(u64, u64, u64)>>);
+
+    /// Renders an almanac, dropping mappings overlapping a previous one as real inputs never
+    /// contain any.
+    fn render_almanac((seeds, maps): &Case) -> String {
+        let seeds = seeds
+            .iter()
+            .map(|(start, length)| format!("{start} {}", length.max(&1)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let maps = MAP_NAMES.iter().zip(maps).map(|(name, mappings)| {
+            let mut kept: Vec<&(u64, u64, u64)> = vec![];
+            for mapping in mappings {
+                let (_, source, length) = *mapping;
+                if length > 0
+                    && kept
+                        .iter()
+                        .all(|(_, s, l)| source + length <= *s || s + l <= source)
+                {
+                    kept.push(mapping);
+                }
+            }
+            let lines = kept
+                .iter()
+                .map(|(destination, source, length)| format!("\n{destination} {source} {length}"))
+                .collect::<String>();
+            format!("{name} map:{lines}")
+        });
+        std::iter::once(format!("seeds: {seeds}"))
+            .chain(maps)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    advent_of_code::differential! {
+        fn range_mapping_matches_point_mapping(cases = 300) {
+            generate: |rng| {
+                let seeds = (0..rng.range(1, 3))
+                    .map(|_| (rng.range(0, 100), rng.range(1, 30)))
+                    .collect();
+                let maps = (0..7)
+                    .map(|_| {
+                        (0..rng.range(0, 4))
+                            .map(|_| (rng.range(0, 100), rng.range(0, 100), rng.range(1, 30)))
+                            .collect()
+                    })
+                    .collect();
+                (seeds, maps)
+            },
+            render: render_almanac,
+            fast: |case| part_two(&render_almanac(case)),
+            naive: |case| {
+                let almanac = Almanac::new_from_str(&render_almanac(case)).unwrap();
+                almanac
+                    .seeds
+                    .chunks(2)
+                    .flat_map(|w| w[0]..w[0] + w[1])
+                    .map(|seed| almanac.map_seed(seed))
+                    .min()
+            },
+        }
+    }
+
     #[test]
     fn test_split_range() {
         assert_eq!(
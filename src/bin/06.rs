@@ -100,6 +100,32 @@ Distance:  9  40  200";
         assert_eq!(compute_all_distances(7), vec![0, 6, 10, 12, 12, 10, 6, 0]);
     }
 
+    fn render_races(races: &[(u64, u64)]) -> String {
+        let (times, records): (Vec<_>, Vec<_>) = races
+            .iter()
+            .map(|(time, record)| (time.to_string(), record.to_string()))
+            .unzip();
+        format!("Time: {}\nDistance: {}", times.join(" "), records.join(" "))
+    }
+
+    advent_of_code::differential! {
+        fn part_one_matches_reference(cases = 500) {
+            generate: |rng| (0..rng.range(1, 4))
+                .map(|_| (rng.range(0, 100), rng.range(0, 2500)))
+                .collect::<Vec<_>>(),
+            render: |races| render_races(races),
+            fast: |races| part_one(&render_races(races)),
+            naive: |races| Some(
+                races
+                    .iter()
+                    .map(|&(time, record)| {
+                        (0..=time).filter(|press| press * (time - press) > record).count() as u32
+                    })
+                    .product(),
+            ),
+        }
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(EXAMPLE);
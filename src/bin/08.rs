@@ -1,12 +1,7 @@
-use std::collections::HashMap;
-
 advent_of_code::solution!(8);
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-struct Node {
-    left: String,
-    right: String,
-}
+use advent_of_code::interner::{Adjacency, Id, Interner};
+use advent_of_code::viz::{self, Color, Dot};
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 enum Direction {
@@ -14,13 +9,32 @@ enum Direction {
     Right,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
-struct Map {
+#[derive(Debug, Clone)]
+struct Map<'a> {
     instructions: Vec<Direction>,
-    nodes: HashMap<String, Node>,
+    labels: Interner<'a>,
+    nodes: Adjacency<2>,
 }
 
-fn parse_map(input: &str) -> Map {
+impl Map<'_> {
+    fn step(&self, node: Id, direction: Direction) -> Id {
+        let [left, right] = self
+            .nodes
+            .get(node)
+            .unwrap_or_else(|| panic!("There should be a node for {}", self.labels.name(node)));
+        match direction {
+            Direction::Left => *left,
+            Direction::Right => *right,
+        }
+    }
+}
+
+fn parse_label(input: &str) -> &str {
+    input.trim_matches(|c: char| !c.is_alphanumeric())
+}
+
+fn parse_map(input: &str) -> Map<'_> {
+    let _span = advent_of_code::span!("parse");
     let mut parts = input.split("\n\n");
     let instructions = parts
         .next()
@@ -33,55 +47,47 @@ fn parse_map(input: &str) -> Map {
         })
         .collect();
 
-    let nodes = parts
-        .next()
-        .expect("There should be nodes")
-        .lines()
-        .map(|line| {
-            let mut parts = line.split(" = ");
-            let key = parts.next().expect("There should be a key");
-            let value = parts.next().expect("There should be a value");
-            let mut parts = value.split(", ");
-            let left = parts
-                .next()
-                .expect("There should be a left")
-                .chars()
-                .skip_while(|c| !c.is_alphanumeric())
-                .take_while(|c| c.is_alphanumeric())
-                .collect();
-            let right = parts
-                .next()
-                .expect("There should be a right")
-                .chars()
-                .skip_while(|c| !c.is_alphanumeric())
-                .take_while(|c| c.is_alphanumeric())
-                .collect();
-            (key.to_string(), Node { left, right })
-        })
-        .collect();
+    let mut labels = Interner::new();
+    let mut nodes = Adjacency::new();
+
+    for line in parts.next().expect("There should be nodes").lines() {
+        let mut parts = line.split(" = ");
+        let key = parts.next().expect("There should be a key");
+        let value = parts.next().expect("There should be a value");
+        let mut parts = value.split(", ");
+        let left = parse_label(parts.next().expect("There should be a left"));
+        let right = parse_label(parts.next().expect("There should be a right"));
+
+        let key = labels.intern(key);
+        let left = labels.intern(left);
+        let right = labels.intern(right);
+        nodes.insert(key, [left, right]);
+    }
 
     Map {
         instructions,
+        labels,
         nodes,
     }
 }
 
 fn count_steps_to_node(map: &Map, from: &str, to: &str) -> u32 {
+    let _span = advent_of_code::span!("walk");
     let mut steps = 0;
-    let mut current_node = from;
+    let mut current_node = map
+        .labels
+        .get(from)
+        .unwrap_or_else(|| panic!("There should be a node for {}", from));
+    let to = map
+        .labels
+        .get(to)
+        .unwrap_or_else(|| panic!("There should be a node for {}", to));
     loop {
         if current_node == to {
             break;
         }
         for direction in &map.instructions {
-            let node = &map
-                .nodes
-                .get(current_node)
-                .unwrap_or_else(|| panic!("There should be a node for {}", current_node));
-            match direction {
-                Direction::Left => current_node = &node.left,
-                Direction::Right => current_node = &node.right,
-            }
+            current_node = map.step(current_node, *direction);
             steps += 1;
         }
     }
@@ -89,8 +95,35 @@ fn count_steps_to_node(map: &Map, from: &str, to: &str) -> u32 {
     steps
 }
 
+/// Exports the node map as a Graphviz graph, start nodes in green and end nodes in red.
+fn visualize_map(map: &Map) {
+    let mut dot = Dot::new("day08");
+    for (id, label) in map.labels.iter() {
+        if label.ends_with('A') {
+            dot.node(label, Some(Color::GREEN));
+        } else if label.ends_with('Z') {
+            dot.node(label, Some(Color::RED));
+        }
+        if let Some([left, right]) = map.nodes.get(id) {
+            dot.edge(label, map.labels.name(*left), Some("L"));
+            dot.edge(label, map.labels.name(*right), Some("R"));
+        }
+    }
+
+    match viz::output_path(DAY, "nodes", "dot").and_then(|path| {
+        dot.write(&path)?;
+        Ok(path)
+    }) {
+        Ok(path) => println!("Wrote {}", path.display()),
+        Err(e) => eprintln!("Failed to write visualisation: {e}"),
+    }
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let map = parse_map(input);
+    if viz::enabled() {
+        visualize_map(&map);
+    }
     Some(count_steps_to_node(&map, "AAA", "ZZZ"))
 }
 
@@ -99,11 +132,18 @@ fn lcm(nums: &[u64]) -> u64 {
 }
 
 fn count_steps_to_node_multi(map: &Map, start: char, end: char) -> u64 {
+    let _span = advent_of_code::span!("walk");
     let mut steps = 0;
+    let is_end: Vec<bool> = map
+        .labels
+        .iter()
+        .map(|(_, label)| label.ends_with(end))
+        .collect();
     let mut current_nodes = map
-        .nodes
-        .keys()
-        .filter(|k| k.ends_with(start))
+        .labels
+        .iter()
+        .filter(|(id, label)| label.ends_with(start) && map.nodes.get(*id).is_some())
+        .map(|(id, _)| id)
         .collect::<Vec<_>>();
 
     let mut reached_nodes_steps = Vec::new();
@@ -111,32 +151,22 @@ fn count_steps_to_node_multi(map: &Map, start: char, end: char) -> u64 {
     loop {
         for direction in &map.instructions {
             for current_node in &mut current_nodes {
-                let node = &map
-                    .nodes
-                    .get(*current_node)
-                    .unwrap_or_else(|| panic!("There should be a node for {}", current_node));
-
-                *current_node = match direction {
-                    Direction::Left => &node.left,
-                    Direction::Right => &node.right,
-                }
+                *current_node = map.step(*current_node, *direction);
             }
 
             steps += 1;
 
-            let node_dones = current_nodes
-                .iter()
-                .filter(|n| n.ends_with(end))
-                .copied()
-                .collect::<Vec<_>>();
-
-            for node in node_dones {
-                reached_nodes_steps.push(steps as u64);
-                let idx = current_nodes.iter().position(|n| *n == node).unwrap();
-                current_nodes.remove(idx);
-            }
+            current_nodes.retain(|node| {
+                if is_end[node.index()] {
+                    reached_nodes_steps.push(steps);
+                    false
+                } else {
+                    true
+                }
+            });
 
             if current_nodes.is_empty() {
+                let _span = advent_of_code::span!("lcm");
                 return lcm(&reached_nodes_steps);
             }
         }
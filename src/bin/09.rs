@@ -1,5 +1,7 @@
 advent_of_code::solution!(9);
 
+use advent_of_code::recorder::Recorder;
+
 fn parse_histories(input: &str) -> Vec<Vec<i64>> {
     input
         .lines()
@@ -14,7 +16,23 @@ fn parse_histories(input: &str) -> Vec<Vec<i64>> {
         .collect()
 }
 
+fn format_sub_histories(sub_histories: &[Vec<i64>]) -> String {
+    sub_histories
+        .iter()
+        .enumerate()
+        .map(|(depth, history)| {
+            let values = history
+                .iter()
+                .map(|n| format!("{n:>4}"))
+                .collect::<String>();
+            format!("{}{values}", " ".repeat(depth * 2))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn create_sub_histories(history: &[i64]) -> Vec<Vec<i64>> {
+    let mut recorder = Recorder::new();
     let mut sub_histories: Vec<Vec<i64>> = Vec::new();
     sub_histories.push(history.to_vec());
 
@@ -26,6 +44,7 @@ fn create_sub_histories(history: &[i64]) -> Vec<Vec<i64>> {
     {
         let last = sub_histories.last().unwrap();
         sub_histories.push(last.windows(2).map(|w| w[1] - w[0]).collect());
+        recorder.text(|| format_sub_histories(&sub_histories));
     }
 
     sub_histories
@@ -2,6 +2,8 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use serde::{Deserialize, Serialize};
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -12,7 +14,8 @@ use std::str::FromStr;
 /// let day = Day::new(8).unwrap();
 /// assert_eq!(day.to_string(), "08")
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
 pub struct Day(u8);
 
 impl Day {
@@ -55,6 +58,20 @@ impl PartialOrd<u8> for Day {
     }
 }
 
+impl TryFrom<u8> for Day {
+    type Error = DayFromStrError;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        Self::new(day).ok_or(DayFromStrError)
+    }
+}
+
+impl From<Day> for u8 {
+    fn from(day: Day) -> Self {
+        day.0
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl FromStr for Day {
@@ -114,6 +131,208 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days, e.g. the days a command runs for.
+///
+/// # Display
+/// This value displays as a list of days and ranges.
+///
+/// ```
+/// # use advent_of_code::{day, DaySet};
+/// let days: DaySet = [day!(1), day!(2), day!(3), day!(7)].into_iter().collect();
+/// assert_eq!(days.to_string(), "1-3,7")
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// A set without any day.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// A set of every day of advent.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the days of the set in ascending order.
+    pub fn iter(&self) -> SelectedDays {
+        SelectedDays {
+            days: all_days(),
+            set: *self,
+        }
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<I: IntoIterator<Item = Day>>(iter: I) -> Self {
+        let mut set = Self::empty();
+        iter.into_iter().for_each(|day| set.insert(day));
+        set
+    }
+}
+
+impl IntoIterator for DaySet {
+    type Item = Day;
+    type IntoIter = SelectedDays;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ranges: Vec<(u8, u8)> = vec![];
+        for day in self.iter() {
+            match ranges.last_mut() {
+                Some((_, end)) if *end + 1 == day.0 => *end = day.0,
+                _ => ranges.push((day.0, day.0)),
+            }
+        }
+        let ranges: Vec<String> = ranges
+            .into_iter()
+            .map(|(start, end)| match end - start {
+                0 => start.to_string(),
+                1 => format!("{start},{end}"),
+                _ => format!("{start}-{end}"),
+            })
+            .collect();
+        f.write_str(&ranges.join(","))
+    }
+}
+
+/// An iterator over the days of a [`DaySet`], in ascending order.
+pub struct SelectedDays {
+    days: AllDays,
+    set: DaySet,
+}
+
+impl Iterator for SelectedDays {
+    type Item = Day;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let set = self.set;
+        self.days.find(|&day| set.contains(day))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Days selected on the command line: a day (`3`), a range (`1-5`), a list of both (`1,4,9`),
+/// `latest`, `unsolved` or `all`.
+///
+/// `latest` and `unsolved` depend on the calendar and on the solved days, which the caller
+/// passes to [`DaySelection::resolve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaySelection {
+    Days(DaySet),
+    /// The last day that unlocked.
+    Latest,
+    /// The days that unlocked and are not solved yet.
+    Unsolved,
+    All,
+}
+
+impl DaySelection {
+    /// Resolves the selection to a set of days. `latest` is the last day that unlocked, if any.
+    pub fn resolve(self, latest: Option<Day>, is_solved: impl Fn(Day) -> bool) -> DaySet {
+        match self {
+            DaySelection::Days(days) => days,
+            DaySelection::Latest => latest.into_iter().collect(),
+            DaySelection::Unsolved => all_days()
+                .take_while(|&day| latest.is_some_and(|latest| day <= latest))
+                .filter(|&day| !is_solved(day))
+                .collect(),
+            DaySelection::All => DaySet::all(),
+        }
+    }
+}
+
+impl From<Day> for DaySelection {
+    fn from(day: Day) -> Self {
+        DaySelection::Days([day].into_iter().collect())
+    }
+}
+
+impl Display for DaySelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySelection::Days(days) => days.fmt(f),
+            DaySelection::Latest => f.write_str("latest"),
+            DaySelection::Unsolved => f.write_str("unsolved"),
+            DaySelection::All => f.write_str("all"),
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "latest" => return Ok(DaySelection::Latest),
+            "unsolved" => return Ok(DaySelection::Unsolved),
+            "all" => return Ok(DaySelection::All),
+            _ => {}
+        }
+
+        let mut days = DaySet::empty();
+        for item in s.split(',') {
+            let (start, end): (Day, Day) = match item.split_once('-') {
+                Some((start, end)) => (start.trim().parse()?, end.trim().parse()?),
+                None => {
+                    let day = item.trim().parse()?;
+                    (day, day)
+                }
+            };
+            if start > end {
+                return Err(DaySelectionFromStrError);
+            }
+            (start.0..=end.0).for_each(|day| days.insert(Day(day)));
+        }
+        Ok(DaySelection::Days(days))
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError;
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting days between 1 and 25 like `3`, `1-5` or `1,4,9`, or `latest`, `unsolved` or `all`",
+        )
+    }
+}
+
+impl From<DayFromStrError> for DaySelectionFromStrError {
+    fn from(_: DayFromStrError) -> Self {
+        DaySelectionFromStrError
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +353,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySelection, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +386,65 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_selections() {
+        let days = |s: &str| match s.parse() {
+            Ok(DaySelection::Days(days)) => days.iter().map(Day::into_inner).collect::<Vec<_>>(),
+            other => panic!("unexpected selection {other:?}"),
+        };
+
+        assert_eq!(days("3"), [3]);
+        assert_eq!(days("1-5"), [1, 2, 3, 4, 5]);
+        assert_eq!(days("9,1,4"), [1, 4, 9]);
+        assert_eq!(days("1-3, 2-4,25"), [1, 2, 3, 4, 25]);
+        assert_eq!(
+            "latest".parse::<DaySelection>().unwrap(),
+            DaySelection::Latest
+        );
+        assert_eq!(
+            "unsolved".parse::<DaySelection>().unwrap(),
+            DaySelection::Unsolved
+        );
+        assert_eq!("all".parse::<DaySelection>().unwrap(), DaySelection::All);
+
+        for invalid in ["", "0", "26", "5-3", "1,", "1-", "x", "1-5-7"] {
+            assert!(invalid.parse::<DaySelection>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn resolves_selections() {
+        let latest = Some(Day(5));
+        let is_solved = |day: Day| day.0 % 2 == 0;
+
+        assert_eq!(DaySelection::All.resolve(latest, is_solved).len(), 25);
+        assert_eq!(
+            DaySelection::Latest.resolve(latest, is_solved).to_string(),
+            "5"
+        );
+        assert_eq!(
+            DaySelection::Unsolved
+                .resolve(latest, is_solved)
+                .to_string(),
+            "1,3,5"
+        );
+        assert!(DaySelection::Unsolved.resolve(None, is_solved).is_empty());
+        assert!(DaySelection::Latest.resolve(None, is_solved).is_empty());
+    }
+
+    #[test]
+    fn displays_day_sets() {
+        let set = |days: &[u8]| days.iter().map(|&day| Day(day)).collect::<DaySet>();
+
+        assert_eq!(set(&[]).to_string(), "");
+        assert_eq!(set(&[1, 2]).to_string(), "1,2");
+        assert_eq!(
+            set(&[1, 2, 3, 7, 9, 10, 11, 25]).to_string(),
+            "1-3,7,9-11,25"
+        );
+        assert_eq!(DaySet::all().to_string(), "1-25");
+    }
 }
 
 /* -------------------------------------------------------------------------- */
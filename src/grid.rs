@@ -0,0 +1,171 @@
+/// A dense two-dimensional grid, the shape most puzzle inputs come in.
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid stored row by row. Cells are addressed as `(x, y)`, with `(0, 0)` in the
+/// top-left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells laid out row by row, returns [`None`] if their count does not
+    /// match the dimensions.
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == width * height).then_some(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the cell at `(x, y)`, or [`None`] if it is out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    /// Returns the cell at `(x, y)` mutably, or [`None`] if it is out of bounds.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Iterates over the rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Iterates over every cell along with its `(x, y)` position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| ((index % self.width, index / self.width), cell))
+    }
+
+    /// Iterates over the in-bounds positions surrounding `(x, y)`, diagonals included.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (-1..=1_isize)
+            .flat_map(|dy| (-1..=1_isize).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| dx != 0 || dy != 0)
+            .filter_map(move |(dx, dy)| {
+                let x = x.checked_add_signed(dx)?;
+                let y = y.checked_add_signed(dy)?;
+                (x < self.width && y < self.height).then_some((x, y))
+            })
+    }
+
+    /// Creates a grid of the same size by applying `f` to every cell.
+    pub fn map<U>(&self, mut f: impl FnMut((usize, usize), &T) -> U) -> Grid<U> {
+        Grid {
+            cells: self
+                .iter()
+                .map(|(position, cell)| f(position, cell))
+                .collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl Grid<char> {
+    /// Parses a block of text into a grid of characters, one row per line.
+    /// Returns [`None`] if the input is empty or its lines have different lengths.
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for line in input.lines() {
+            let len = cells.len();
+            cells.extend(line.chars());
+            if *width.get_or_insert(cells.len() - len) != cells.len() - len {
+                return None;
+            }
+            height += 1;
+        }
+
+        Self::from_cells(width?, height, cells)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is out of the grid bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is out of the grid bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+
+    #[test]
+    fn parse_and_display() {
+        let grid = Grid::parse("ab.\n.#c\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], '#');
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "ab.\n.#c");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_input() {
+        assert_eq!(Grid::parse("abc\nde"), None);
+        assert_eq!(Grid::parse(""), None);
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbours(0, 0).count(), 3);
+        assert_eq!(grid.neighbours(1, 1).count(), 8);
+        assert_eq!(grid.neighbours(2, 1).count(), 5);
+    }
+}
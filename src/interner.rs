@@ -0,0 +1,239 @@
+/// Interning of short textual labels into dense integer identifiers.
+///
+/// Walk-style puzzles name their nodes with short labels (`AAA`, `11B`, ...). Interning them once
+/// while parsing lets the actual walk run on plain array indexing through an [`Adjacency`] table.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Index;
+
+/// Number of distinct labels made of three characters in `[0-9A-Z]`.
+pub const PACKED_LABELS: usize = 36 * 36 * 36;
+
+/// A dense identifier handed out by an [`Interner`], starting at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Id(u32);
+
+impl Id {
+    const VACANT: Id = Id(u32::MAX);
+
+    /// Converts the [`Id`] into an index usable with slices.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Converts the [`Id`] into an [`u32`].
+    pub fn into_inner(self) -> u32 {
+        self.0
+    }
+
+    /// Converts the [`Id`] into an [`u16`], returns [`None`] if it does not fit.
+    pub fn to_u16(self) -> Option<u16> {
+        self.0.try_into().ok()
+    }
+}
+
+impl Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Packs a label of exactly three characters in `[0-9A-Z]` into an integer below
+/// [`PACKED_LABELS`], returns [`None`] for any other label.
+///
+/// ```
+/// # use advent_of_code::interner::pack_label;
+/// assert_eq!(pack_label("000"), Some(0));
+/// assert_eq!(pack_label("ZZZ"), Some(46655));
+/// assert_eq!(pack_label("zzz"), None);
+/// ```
+pub fn pack_label(label: &str) -> Option<u16> {
+    let &[a, b, c] = label.as_bytes() else {
+        return None;
+    };
+    let digit = |byte: u8| match byte {
+        b'0'..=b'9' => Some(u16::from(byte - b'0')),
+        b'A'..=b'Z' => Some(u16::from(byte - b'A') + 10),
+        _ => None,
+    };
+    Some((digit(a)? * 36 + digit(b)?) * 36 + digit(c)?)
+}
+
+/// Maps labels borrowed from the puzzle input to dense [`Id`]s, without copying them.
+///
+/// Three character alphanumeric labels go through a direct lookup table, anything else falls
+/// back to hashing.
+#[derive(Debug, Default, Clone)]
+pub struct Interner<'a> {
+    names: Vec<&'a str>,
+    packed: Vec<Id>,
+    others: HashMap<&'a str, Id>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the [`Id`] of `label`, allocating the next free one if it was never seen.
+    pub fn intern(&mut self, label: &'a str) -> Id {
+        if let Some(id) = self.get(label) {
+            return id;
+        }
+
+        let id = Id(u32::try_from(self.names.len()).expect("too many interned labels"));
+        self.names.push(label);
+
+        match pack_label(label) {
+            Some(packed) => {
+                if self.packed.is_empty() {
+                    self.packed = vec![Id::VACANT; PACKED_LABELS];
+                }
+                self.packed[packed as usize] = id;
+            }
+            None => {
+                self.others.insert(label, id);
+            }
+        }
+
+        id
+    }
+
+    /// Returns the [`Id`] of `label` if it has been interned.
+    pub fn get(&self, label: &str) -> Option<Id> {
+        match pack_label(label) {
+            Some(packed) => self
+                .packed
+                .get(packed as usize)
+                .copied()
+                .filter(|id| *id != Id::VACANT),
+            None => self.others.get(label).copied(),
+        }
+    }
+
+    /// Returns the label an [`Id`] was created from.
+    ///
+    /// # Panics
+    /// Panics if `id` was not created by this interner.
+    pub fn name(&self, id: Id) -> &'a str {
+        self.names[id.index()]
+    }
+
+    /// Number of interned labels, which is also the next [`Id`] that will be handed out.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Iterates over every interned label along with its [`Id`], in allocation order.
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &'a str)> + '_ {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (Id(index as u32), *name))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A dense table storing `N` outgoing edges for every [`Id`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Adjacency<const N: usize> {
+    edges: Vec<[Id; N]>,
+}
+
+impl<const N: usize> Default for Adjacency<N> {
+    fn default() -> Self {
+        Self { edges: vec![] }
+    }
+}
+
+impl<const N: usize> Adjacency<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the edges leaving `from`, growing the table if needed.
+    pub fn insert(&mut self, from: Id, to: [Id; N]) {
+        if self.edges.len() <= from.index() {
+            self.edges.resize(from.index() + 1, [Id::VACANT; N]);
+        }
+        self.edges[from.index()] = to;
+    }
+
+    /// Returns the edges leaving `from`, if they have been set.
+    pub fn get(&self, from: Id) -> Option<&[Id; N]> {
+        self.edges
+            .get(from.index())
+            .filter(|edges| edges.iter().all(|id| *id != Id::VACANT))
+    }
+
+    /// Number of slots in the table, i.e. one past the highest [`Id`] inserted.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Iterates over every node with edges set.
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &[Id; N])> + '_ {
+        self.edges
+            .iter()
+            .enumerate()
+            .filter(|(_, edges)| edges.iter().all(|id| *id != Id::VACANT))
+            .map(|(index, edges)| (Id(index as u32), edges))
+    }
+}
+
+impl<const N: usize> Index<Id> for Adjacency<N> {
+    type Output = [Id; N];
+
+    fn index(&self, from: Id) -> &Self::Output {
+        self.get(from)
+            .unwrap_or_else(|| panic!("There should be edges for {from}"))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Adjacency, Interner};
+
+    #[test]
+    fn interns_packed_and_other_labels() {
+        let input = String::from("AAA 11B node AAA node");
+        let mut interner = Interner::new();
+        let ids: Vec<_> = input
+            .split(' ')
+            .map(|label| interner.intern(label))
+            .collect();
+
+        assert_eq!(ids[0], ids[3]);
+        assert_eq!(ids[2], ids[4]);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.name(ids[1]), "11B");
+        assert_eq!(interner.get("node"), Some(ids[2]));
+        assert_eq!(interner.get("ZZZ"), None);
+    }
+
+    #[test]
+    fn adjacency_lookup() {
+        let mut interner = Interner::new();
+        let [a, b, c] = ["AAA", "BBB", "CCC"].map(|label| interner.intern(label));
+
+        let mut table = Adjacency::new();
+        table.insert(c, [a, b]);
+
+        assert_eq!(table[c], [a, b]);
+        assert_eq!(table.get(a), None);
+        assert_eq!(table.iter().count(), 1);
+    }
+}
@@ -1,4 +1,18 @@
+pub mod allocations;
+pub mod answer;
 mod day;
+pub mod grid;
+pub mod interner;
+pub mod matcher;
+pub mod memo;
+pub mod ocr;
+pub mod overflow;
+pub mod profile;
+pub mod recorder;
+pub mod rng;
 pub mod template;
+#[cfg(feature = "test_support")]
+pub mod testing;
+pub mod viz;
 
 pub use day::*;
@@ -1,92 +1,56 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
-use args::{parse, AppArguments};
+use advent_of_code::template::cli::{App, AppCommand};
+use advent_of_code::template::commands::{
+    all, completions, config, dashboard, download, fuzz, leaderboard, read, scaffold, solve, stars,
+    stats, test, verify, wait,
+};
+use advent_of_code::{template, DaySelection};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
 
-mod args {
-    use std::process;
-
-    use advent_of_code::Day;
-
-    pub enum AppArguments {
-        Download {
-            day: Day,
-        },
-        Read {
-            day: Day,
-        },
-        Scaffold {
-            day: Day,
-        },
-        Solve {
-            day: Day,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
-        },
-        All {
-            release: bool,
-            time: bool,
-        },
-    }
-
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
-            },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
-
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+fn main() {
+    let app = App::parse();
+
+    // overrides have to be applied before the configuration is first read.
+    for setting in &app.set {
+        if let Err(message) = template::config::set_override(setting) {
+            App::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("invalid value '{setting}' for '--set <KEY=VALUE>': {message}"),
+                )
+                .exit();
         }
-
-        Ok(app_args)
     }
-}
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    match app.command {
+        AppCommand::All {
+            days,
+            release,
+            time,
+            table,
+        } => {
+            let table = table.resolve(template::config::get().bench.table());
+            all::handle(days.unwrap_or(DaySelection::All), release, time, &table);
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
+        AppCommand::Completions { shell } => completions::handle(shell),
+        AppCommand::Config { .. } => config::handle(),
+        AppCommand::Dashboard => dashboard::handle(),
+        AppCommand::Fuzz {
+            release,
+            iterations,
+        } => fuzz::handle(release, iterations),
+        AppCommand::Stars {
+            leaderboard,
+            user_id,
+        } => stars::handle(leaderboard, user_id),
+        AppCommand::Leaderboard { id, options } => leaderboard::handle(id, options),
+        AppCommand::Stats { year } => stats::handle(year),
+        AppCommand::Test { days } => test::handle(days.unwrap_or(DaySelection::All)),
+        AppCommand::Verify { days } => verify::handle(days.unwrap_or(DaySelection::All)),
+        AppCommand::Wait { day } => wait::handle(day),
+        AppCommand::Download { days } => download::handle(days),
+        AppCommand::Read { day, offline } => read::handle(day, offline),
+        AppCommand::Scaffold { days } => scaffold::handle(days),
+        AppCommand::Solve { days, options } => solve::handle(days, options),
     };
 }
@@ -0,0 +1,249 @@
+/// Multi-pattern string matching built on an Aho–Corasick automaton.
+///
+/// The automaton works on bytes, which keeps it UTF-8 safe: every pattern is valid UTF-8, so a
+/// match inside a valid `&str` always starts and ends on a char boundary. Haystacks can be either
+/// `&str` or `&[u8]`.
+use std::collections::VecDeque;
+
+const ALPHABET: usize = 256;
+const ROOT: u32 = 0;
+
+/// A single occurrence of a pattern inside a haystack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the matched pattern, in the order the patterns were given.
+    pub pattern: usize,
+    /// Byte offset of the first byte of the match.
+    pub start: usize,
+    /// Byte offset one past the last byte of the match.
+    pub end: usize,
+}
+
+/// A compiled set of patterns that can be searched for in a single pass.
+///
+/// ```
+/// # use advent_of_code::matcher::Matcher;
+/// let matcher = Matcher::new(["one", "two", "three"]);
+/// let (first, last) = matcher.find_first_and_last("xtwone3").unwrap();
+/// assert_eq!((first.pattern, last.pattern), (1, 0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    /// Dense transition table, `ALPHABET` entries per state.
+    transitions: Vec<u32>,
+    /// Patterns (by index) ending in each state, including those inherited through failure links.
+    outputs: Vec<Vec<usize>>,
+    lengths: Vec<usize>,
+}
+
+impl Matcher {
+    /// Compiles the given patterns. Empty patterns are ignored.
+    pub fn new<I, P>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<str>,
+    {
+        let mut transitions = vec![ROOT; ALPHABET];
+        let mut outputs = vec![vec![]];
+        let mut lengths = vec![];
+        // `ROOT` doubles as "no transition" while building the trie; the root itself is never a
+        // transition target, so the sentinel is unambiguous.
+        let mut trie_edges = vec![[false; ALPHABET]];
+
+        for (index, pattern) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref().as_bytes();
+            lengths.push(pattern.len());
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut state = ROOT as usize;
+            for &byte in pattern {
+                let slot = state * ALPHABET + byte as usize;
+                if !trie_edges[state][byte as usize] {
+                    let next = outputs.len();
+                    transitions[slot] = next as u32;
+                    trie_edges[state][byte as usize] = true;
+                    transitions.extend([ROOT; ALPHABET]);
+                    outputs.push(vec![]);
+                    trie_edges.push([false; ALPHABET]);
+                }
+                state = transitions[slot] as usize;
+            }
+            outputs[state].push(index);
+        }
+
+        // Breadth-first pass turning the trie into a complete DFA: missing edges follow the
+        // failure link of their state, and outputs are merged along those links.
+        let mut failure = vec![ROOT; outputs.len()];
+        let mut queue = VecDeque::new();
+        for byte in 0..ALPHABET {
+            if trie_edges[0][byte] {
+                queue.push_back(transitions[byte] as usize);
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            let fail = failure[state] as usize;
+            let inherited = outputs[fail].clone();
+            outputs[state].extend(inherited);
+
+            for byte in 0..ALPHABET {
+                let slot = state * ALPHABET + byte;
+                let fail_target = transitions[fail * ALPHABET + byte];
+                if trie_edges[state][byte] {
+                    let next = transitions[slot] as usize;
+                    failure[next] = fail_target;
+                    queue.push_back(next);
+                } else {
+                    transitions[slot] = fail_target;
+                }
+            }
+        }
+
+        Self {
+            transitions,
+            outputs,
+            lengths,
+        }
+    }
+
+    /// Number of patterns the matcher was built with.
+    pub fn pattern_count(&self) -> usize {
+        self.lengths.len()
+    }
+
+    /// Iterates over every match in `haystack`, including overlapping ones, ordered by end offset.
+    pub fn find_iter<'m, 'h, H>(&'m self, haystack: &'h H) -> FindIter<'m, 'h>
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        FindIter {
+            matcher: self,
+            haystack: haystack.as_ref(),
+            position: 0,
+            state: ROOT,
+            pending: 0,
+        }
+    }
+
+    /// Returns the match starting the earliest in `haystack`.
+    /// Ties on the start offset resolve to the longest match.
+    pub fn find_first<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Option<Match> {
+        self.find_first_and_last(haystack).map(|(first, _)| first)
+    }
+
+    /// Returns the match starting the latest in `haystack`.
+    /// Ties on the start offset resolve to the longest match.
+    pub fn find_last<H: AsRef<[u8]> + ?Sized>(&self, haystack: &H) -> Option<Match> {
+        self.find_first_and_last(haystack).map(|(_, last)| last)
+    }
+
+    /// Returns both the earliest and the latest starting match, scanning `haystack` only once.
+    pub fn find_first_and_last<H>(&self, haystack: &H) -> Option<(Match, Match)>
+    where
+        H: AsRef<[u8]> + ?Sized,
+    {
+        let prefer = |current: Match, candidate: Match, later: bool| {
+            let ordering = candidate.start.cmp(&current.start);
+            let better_start = if later {
+                ordering.is_gt()
+            } else {
+                ordering.is_lt()
+            };
+            if better_start || (ordering.is_eq() && candidate.end > current.end) {
+                candidate
+            } else {
+                current
+            }
+        };
+
+        self.find_iter(haystack).fold(None, |acc, m| match acc {
+            None => Some((m, m)),
+            Some((first, last)) => Some((prefer(first, m, false), prefer(last, m, true))),
+        })
+    }
+}
+
+/// Iterator over overlapping matches, created by [`Matcher::find_iter`].
+pub struct FindIter<'m, 'h> {
+    matcher: &'m Matcher,
+    haystack: &'h [u8],
+    position: usize,
+    state: u32,
+    /// Number of outputs of `state` not yielded yet.
+    pending: usize,
+}
+
+impl Iterator for FindIter<'_, '_> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.pending > 0 {
+                self.pending -= 1;
+                let pattern = self.matcher.outputs[self.state as usize][self.pending];
+                let end = self.position;
+                return Some(Match {
+                    pattern,
+                    start: end - self.matcher.lengths[pattern],
+                    end,
+                });
+            }
+
+            let &byte = self.haystack.get(self.position)?;
+            self.state = self.matcher.transitions[self.state as usize * ALPHABET + byte as usize];
+            self.position += 1;
+            self.pending = self.matcher.outputs[self.state as usize].len();
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Match, Matcher};
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let matcher = Matcher::new(["he", "she", "his", "hers"]);
+        let mut matches: Vec<_> = matcher
+            .find_iter("ushers")
+            .map(|m| (m.pattern, m.start, m.end))
+            .collect();
+        matches.sort_unstable();
+        assert_eq!(matches, vec![(0, 2, 4), (1, 1, 4), (3, 2, 6)]);
+    }
+
+    #[test]
+    fn first_and_last_prefer_start_offset() {
+        let matcher = Matcher::new(["abcd", "bc"]);
+        let (first, last) = matcher.find_first_and_last("xabcd").unwrap();
+        assert_eq!(
+            first,
+            Match {
+                pattern: 0,
+                start: 1,
+                end: 5
+            }
+        );
+        assert_eq!(last.pattern, 1);
+    }
+
+    #[test]
+    fn is_utf8_safe() {
+        let matcher = Matcher::new(["un", "deux", "trois", "é"]);
+        let haystack = "élan deux→trois";
+        let (first, last) = matcher.find_first_and_last(haystack).unwrap();
+        assert_eq!(&haystack[first.start..first.end], "é");
+        assert_eq!(&haystack[last.start..last.end], "trois");
+    }
+
+    #[test]
+    fn no_match() {
+        let matcher = Matcher::new(["abc", ""]);
+        assert_eq!(matcher.find_first("ab"), None);
+        assert_eq!(matcher.pattern_count(), 2);
+    }
+}
@@ -0,0 +1,253 @@
+/// Caches for memoised recursion and state searches.
+///
+/// A [`Memo`] maps keys to computed values with a fast, non-cryptographic hasher, which suits the
+/// small integer and tuple keys of most puzzles far better than the default SipHash. The runner
+/// clears all memos before each benchmark sample, so samples do not just read the answers cached
+/// by a previous one, and debug builds report how often the memos were hit.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Incremented to clear every memo, which notice it on their next access.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+/// A hasher mixing in one word at a time, as done by rustc's `FxHasher`. Fast for integer keys,
+/// but not resistant to crafted collisions, which puzzle inputs do not contain.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FastHasher {
+    hash: u64,
+}
+
+impl FastHasher {
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FastHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u8(&mut self, n: u8) {
+        self.add(n.into());
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.add(n.into());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.add(n.into());
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.add(n);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.add(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// Builds [`FastHasher`]s for hash maps and sets.
+pub type FastBuildHasher = BuildHasherDefault<FastHasher>;
+
+/// A [`HashMap`] using the [`FastHasher`].
+pub type FastHashMap<K, V> = HashMap<K, V, FastBuildHasher>;
+
+/// Lookups of all memos, counted in debug builds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl Stats {
+    /// Share of lookups answered from a memo.
+    pub fn hit_rate(&self) -> f64 {
+        self.hits as f64 / (self.hits + self.misses).max(1) as f64
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )
+    }
+}
+
+/// Clears every memo, including the ones in statics. Called by the runner before each run of a
+/// part; the memos are cleared on their next access.
+pub fn clear_all() {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Returns the lookups counted since the last call, [`None`] if there were none, which is always
+/// the case in release builds.
+pub fn take_stats() -> Option<Stats> {
+    let stats = Stats {
+        hits: HITS.swap(0, Ordering::Relaxed),
+        misses: MISSES.swap(0, Ordering::Relaxed),
+    };
+    (stats != Stats::default()).then_some(stats)
+}
+
+fn count(counter: &AtomicU64) {
+    if cfg!(debug_assertions) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A cache of computed values.
+///
+/// ```ignore
+/// fn arrangements(memo: &mut Memo<(usize, usize), u64>, springs: usize, groups: usize) -> u64 {
+///     memo.get_or_insert_with((springs, groups), |memo| {
+///         // ... recursive calls with `memo` ...
+///     })
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    map: FastHashMap<K, V>,
+    /// Generation the entries were computed in, see [`clear_all`].
+    generation: u64,
+}
+
+impl<K, V> Memo<K, V> {
+    /// Creates an empty memo, also usable to initialise a `static` or `thread_local!`.
+    pub const fn new() -> Self {
+        Self {
+            map: HashMap::with_hasher(BuildHasherDefault::new()),
+            generation: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Drops the entries of a previous generation.
+    fn refresh(&mut self) {
+        let generation = GENERATION.load(Ordering::Relaxed);
+        if self.generation != generation {
+            self.map.clear();
+            self.generation = generation;
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    /// Returns the cached value of `key`.
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        self.refresh();
+        let value = self.map.get(key).cloned();
+        count(if value.is_some() { &HITS } else { &MISSES });
+        value
+    }
+
+    /// Caches the value of `key` and returns it.
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        self.refresh();
+        self.map.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the cached value of `key`, or computes it with `f` and caches it. `f` gets the memo
+    /// back, so it can make recursive calls that use it too.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        match self.get(&key) {
+            Some(value) => value,
+            None => {
+                let value = f(self);
+                self.insert(key, value)
+            }
+        }
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::hash::{BuildHasher, Hasher};
+
+    use super::{clear_all, take_stats, FastBuildHasher, FastHasher, Memo};
+
+    fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(n, |memo| fibonacci(memo, n - 1) + fibonacci(memo, n - 2))
+    }
+
+    #[test]
+    fn memoises_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+        assert_eq!(memo.len(), 89);
+        assert_eq!(memo.get(&10), Some(55));
+        assert_eq!(memo.get(&100), None);
+
+        clear_all();
+        assert_eq!(memo.get(&10), None);
+        assert!(memo.is_empty());
+        // other tests may count lookups at the same time.
+        assert!(take_stats().is_some_and(|stats| stats.hits > 0 && stats.misses > 0));
+    }
+
+    #[test]
+    fn hashes_words() {
+        let hash = |f: fn(&mut FastHasher)| {
+            let mut hasher = FastBuildHasher::default().build_hasher();
+            f(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(|h| h.write_u32(7)), hash(|h| h.write_u64(7)));
+        assert_ne!(hash(|h| h.write_u64(1)), hash(|h| h.write_u64(2)));
+        assert_ne!(
+            hash(|h| {
+                h.write_u8(1);
+                h.write_u8(2);
+            }),
+            hash(|h| {
+                h.write_u8(2);
+                h.write_u8(1);
+            })
+        );
+        assert_eq!(hash(|h| h.write(&[7, 0])), hash(|h| h.write_u8(7)));
+    }
+}
@@ -0,0 +1,264 @@
+/// Reads the block letters some puzzles draw instead of printing their answer.
+///
+/// Advent of Code uses two fonts: letters 4 pixels wide and 6 pixels tall, e.g. in 2016 day 8 or
+/// 2022 day 10, and letters 6 pixels wide and 10 pixels tall in 2018 day 10. Letters are split on
+/// columns without lit pixels, so the spacing between them does not matter.
+use crate::grid::Grid;
+
+const FONT_6: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Whether a character of a picture is a lit pixel: anything but `.` and whitespace, so both
+/// `#` / `.` and `█` / ` ` pictures can be read.
+fn is_lit(c: char) -> bool {
+    c != '.' && !c.is_whitespace()
+}
+
+/// Pixels of a picture, rows without lit pixels above and below the letters are removed.
+fn pixels(picture: &str) -> Vec<Vec<bool>> {
+    let mut rows: Vec<Vec<bool>> = picture
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+    let is_blank = |row: &Vec<bool>| !row.contains(&true);
+    while rows.last().is_some_and(is_blank) {
+        rows.pop();
+    }
+    let leading = rows.iter().take_while(|row| is_blank(row)).count();
+    rows.drain(..leading);
+
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut rows {
+        row.resize(width, false);
+    }
+    rows
+}
+
+/// Splits the pixels into the columns of single letters, on columns without lit pixels.
+fn split_letters(rows: &[Vec<bool>]) -> Vec<(usize, usize)> {
+    let width = rows.first().map_or(0, Vec::len);
+    let is_blank = |x: usize| rows.iter().all(|row| !row[x]);
+
+    let mut letters = vec![];
+    let mut start = None;
+    for x in 0..=width {
+        match (start, x == width || is_blank(x)) {
+            (None, false) => start = Some(x),
+            (Some(from), true) => {
+                letters.push((from, x));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    letters
+}
+
+/// Renders the columns `from..to` of the pixels the way glyphs are stored in a font.
+fn glyph(rows: &[Vec<bool>], (from, to): (usize, usize)) -> String {
+    rows.iter()
+        .map(|row| {
+            row[from..to]
+                .iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Removes the columns without lit pixels around a glyph of a font, e.g. left of `I`.
+fn trim_glyph(glyph: &str) -> String {
+    let rows: Vec<Vec<bool>> = glyph
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+    match split_letters(&rows)[..] {
+        [first, .., last] => self::glyph(&rows, (first.0, last.1)),
+        [only] => self::glyph(&rows, only),
+        [] => String::new(),
+    }
+}
+
+/// Reads the letters of a picture in one of the fonts of Advent of Code, [`None`] if the
+/// picture has no letters or one of them is unknown.
+pub fn read(picture: &str) -> Option<String> {
+    let rows = pixels(picture);
+    let font = match rows.len() {
+        6 => FONT_6,
+        10 => FONT_10,
+        _ => return None,
+    };
+
+    let letters = split_letters(&rows);
+    if letters.is_empty() {
+        return None;
+    }
+    letters
+        .into_iter()
+        .map(|columns| {
+            let glyph = glyph(&rows, columns);
+            font.iter()
+                .find(|(_, known)| trim_glyph(known) == glyph)
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+/// Draws a grid of pixels with `#` for lit and `.` for dark pixels, the way [`read`] expects it.
+pub fn render(grid: &Grid<bool>) -> String {
+    grid.rows()
+        .map(|row| {
+            row.iter()
+                .map(|&lit| if lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read, render, FONT_10, FONT_6};
+    use crate::grid::Grid;
+
+    /// Draws `text` in a font, one blank column between letters like on the website.
+    fn draw(font: &[(char, &str)], text: &str) -> String {
+        let glyphs: Vec<Vec<&str>> = text
+            .chars()
+            .map(|c| {
+                let (_, glyph) = font.iter().find(|(letter, _)| *letter == c).unwrap();
+                glyph.lines().collect()
+            })
+            .collect();
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| format!("{}.", glyph[y]))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn reads_small_letters() {
+        let all: String = FONT_6.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(read(&draw(FONT_6, &all)), Some(all));
+        assert_eq!(
+            read(&draw(FONT_6, "HI").replace('#', "█").replace('.', " ")),
+            Some("HI".into())
+        );
+        assert_eq!(
+            read(&format!(
+                "\n......\n{}\n",
+                draw(FONT_6, "ZERO").replace("\n", "..\n")
+            )),
+            Some("ZERO".into())
+        );
+    }
+
+    #[test]
+    fn reads_large_letters() {
+        let all: String = FONT_10.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(read(&draw(FONT_10, &all)), Some(all));
+    }
+
+    #[test]
+    fn rejects_unknown_pictures() {
+        assert_eq!(read(""), None);
+        assert_eq!(read("......\n".repeat(6).as_str()), None);
+        assert_eq!(read("#\n#\n#\n#\n#\n#"), None);
+        assert_eq!(read("#..#\n#..#"), None);
+    }
+
+    #[test]
+    fn renders_grids() {
+        let grid = Grid::from_cells(3, 2, vec![true, false, true, false, true, false]).unwrap();
+        assert_eq!(render(&grid), "#.#\n.#.");
+    }
+}
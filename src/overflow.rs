@@ -0,0 +1,86 @@
+/// Finds answers that were cut off by a conversion to a narrower integer type.
+///
+/// A solution that computes with `u64` and returns `u32` gets a wrong answer as soon as a value
+/// does not fit, without any error. Conversions written with [`narrow`] instead of `as` are
+/// checked in debug builds: values that change are remembered while the part runs, and the runner
+/// warns if the answer of the part is one of them.
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use num::traits::AsPrimitive;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TRUNCATIONS: Mutex<Vec<Truncation>> = Mutex::new(vec![]);
+
+/// A value that changed when it was converted to a narrower type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Truncation {
+    pub wide: i128,
+    pub narrow: i128,
+}
+
+/// Whether conversions are checked, i.e. the part runs in a debug build and is not being
+/// benchmarked.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Checks conversions from now on, only has an effect in debug builds. Called by the runner.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled && cfg!(debug_assertions), Ordering::Relaxed);
+}
+
+/// Converts an integer to another integer type like `as` does, e.g. `narrow::<u32, _>(total)`.
+/// When checked, a value that does not survive the conversion is remembered.
+pub fn narrow<T, U>(value: U) -> T
+where
+    T: AsPrimitive<i128>,
+    U: AsPrimitive<T> + AsPrimitive<i128>,
+{
+    let narrow: T = value.as_();
+    if enabled() {
+        let (wide, narrow): (i128, i128) = (value.as_(), narrow.as_());
+        if wide != narrow {
+            let mut truncations = TRUNCATIONS.lock().unwrap();
+            truncations.push(Truncation { wide, narrow });
+        }
+    }
+    narrow
+}
+
+/// Returns the conversions that changed a value since the last call.
+pub fn take_truncations() -> Vec<Truncation> {
+    std::mem::take(&mut *TRUNCATIONS.lock().unwrap())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{narrow, set_enabled, take_truncations, Truncation};
+
+    #[test]
+    fn remembers_truncations() {
+        set_enabled(true);
+        assert_eq!(narrow::<u32, _>(7_u64), 7);
+        assert_eq!(narrow::<u32, _>(u64::from(u32::MAX) + 3), 2);
+        assert_eq!(narrow::<u8, _>(-1_i64), 255);
+        set_enabled(false);
+        assert_eq!(narrow::<u32, _>(u64::MAX), u32::MAX);
+
+        assert_eq!(
+            take_truncations(),
+            [
+                Truncation {
+                    wide: i128::from(u32::MAX) + 3,
+                    narrow: 2
+                },
+                Truncation {
+                    wide: -1,
+                    narrow: 255
+                },
+            ]
+        );
+        assert!(take_truncations().is_empty());
+    }
+}
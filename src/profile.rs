@@ -0,0 +1,318 @@
+/// Lightweight profiling of where a part spends its time.
+///
+/// Solutions open named spans with the [`span!`](crate::span) macro. Spans nest, and repeated
+/// spans with the same name under the same parent are merged. Profiling is only active when the
+/// solution runs with `--profile`, and never while benchmarking: a disabled span costs a single
+/// atomic load, so spans can stay in solution code.
+///
+/// Once a part has run, the runner prints the recorded tree and writes it as folded stacks to
+/// `data/profile/`, the format read by `flamegraph.pl` and `inferno-flamegraph`.
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::template::{ansi, config, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static TREE: RefCell<Tree> = RefCell::new(Tree::new());
+}
+
+/// Whether profiling was requested, i.e. the solution runs with `--profile` and is not being
+/// benchmarked.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// Not part of the public API, toggled by the runner.
+#[doc(hidden)]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    // the root of the tree covers the time profiling is enabled, i.e. the whole part.
+    TREE.with(|tree| {
+        let mut tree = tree.borrow_mut();
+        match (enabled, tree.started.take()) {
+            (true, _) => tree.started = Some(Instant::now()),
+            (false, Some(started)) => tree.nodes[0].total += started.elapsed(),
+            (false, None) => {}
+        }
+    });
+}
+
+/// Opens a profiling span that lasts until the returned guard is dropped.
+///
+/// ```
+/// fn parse(input: &str) -> Vec<u32> {
+///     let _span = advent_of_code::span!("parse");
+///     input.lines().filter_map(|l| l.parse().ok()).collect()
+/// }
+/// ```
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        $crate::profile::Span::enter($name)
+    };
+}
+
+/// Guard of an open span, see [`span!`](crate::span).
+#[must_use = "the span closes as soon as the guard is dropped"]
+pub struct Span {
+    start: Option<Instant>,
+}
+
+impl Span {
+    pub fn enter(name: &'static str) -> Self {
+        if !enabled() {
+            return Self { start: None };
+        }
+        TREE.with(|tree| tree.borrow_mut().enter(name));
+        Self {
+            start: Some(Instant::now()),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let elapsed = start.elapsed();
+            TREE.with(|tree| tree.borrow_mut().exit(elapsed));
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    name: &'static str,
+    total: Duration,
+    calls: u64,
+    children: Vec<usize>,
+}
+
+/// Spans recorded on one thread. Node `0` is the root, standing for the whole part.
+#[derive(Debug, Clone)]
+struct Tree {
+    nodes: Vec<Node>,
+    /// Path from the root to the innermost open span.
+    stack: Vec<usize>,
+    /// When profiling was enabled, if it still is.
+    started: Option<Instant>,
+}
+
+impl Tree {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node {
+                name: "",
+                total: Duration::ZERO,
+                calls: 1,
+                children: vec![],
+            }],
+            stack: vec![0],
+            started: None,
+        }
+    }
+
+    fn enter(&mut self, name: &'static str) {
+        let parent = *self.stack.last().unwrap_or(&0);
+        let existing = self.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.nodes[child].name == name);
+        let node = existing.unwrap_or_else(|| {
+            self.nodes.push(Node {
+                name,
+                total: Duration::ZERO,
+                calls: 0,
+                children: vec![],
+            });
+            let node = self.nodes.len() - 1;
+            self.nodes[parent].children.push(node);
+            node
+        });
+        self.nodes[node].calls += 1;
+        self.stack.push(node);
+    }
+
+    fn exit(&mut self, elapsed: Duration) {
+        // the root is never popped, unbalanced exits cannot happen with guards.
+        if self.stack.len() > 1 {
+            let node = self.stack.pop().unwrap_or(0);
+            self.nodes[node].total += elapsed;
+        }
+    }
+}
+
+/// Takes the spans recorded on the current thread since the last call, returns [`None`] if no
+/// span was recorded.
+pub fn take_report() -> Option<Report> {
+    let tree = TREE.with(|tree| tree.replace(Tree::new()));
+    (tree.nodes.len() > 1).then_some(Report { nodes: tree.nodes })
+}
+
+/// A tree of recorded spans.
+#[derive(Debug, Clone)]
+pub struct Report {
+    nodes: Vec<Node>,
+}
+
+impl Report {
+    fn self_time(&self, node: usize) -> Duration {
+        let children = self.nodes[node]
+            .children
+            .iter()
+            .map(|&child| self.nodes[child].total)
+            .sum();
+        self.nodes[node].total.saturating_sub(children)
+    }
+
+    /// Total duration of the top-level spans named `name`, e.g. `parse`.
+    pub fn span(&self, name: &str) -> Option<Duration> {
+        self.nodes[0]
+            .children
+            .iter()
+            .map(|&child| &self.nodes[child])
+            .find(|node| node.name == name)
+            .map(|node| node.total)
+    }
+
+    /// Renders the tree with the duration, share of the total and number of calls of every span.
+    pub fn tree(&self, root: &str) -> String {
+        const BAR_WIDTH: f64 = 20.0;
+        let total = self.nodes[0].total.as_secs_f64().max(f64::EPSILON);
+        let mut out = String::new();
+
+        let mut visit = vec![(0, 0)];
+        while let Some((node, depth)) = visit.pop() {
+            let Node {
+                name,
+                total: duration,
+                calls,
+                children,
+            } = &self.nodes[node];
+            let share = duration.as_secs_f64() / total;
+            let label = format!(
+                "{:indent$}{}",
+                "",
+                if node == 0 { root } else { name },
+                indent = depth * 2
+            );
+            let bar = "█".repeat((share * BAR_WIDTH).round() as usize);
+            let _ = write!(out, "{label:<32} {duration:>10.1?} {:>5.1}%", share * 100.0);
+            match calls {
+                1 if bar.is_empty() => {}
+                1 => out.push_str(&format!(" {bar}")),
+                _ => out.push_str(&format!(" {bar:<20} ×{calls}")),
+            }
+            out.push('\n');
+            visit.extend(children.iter().rev().map(|&child| (child, depth + 1)));
+        }
+        out
+    }
+
+    /// Renders the tree as folded stacks, one `root;span;nested <self time in ns>` line per span.
+    pub fn folded(&self, root: &str) -> String {
+        let mut out = String::new();
+        let mut visit = vec![(0, root.to_string())];
+        while let Some((node, path)) = visit.pop() {
+            let nanos = self.self_time(node).as_nanos();
+            if nanos > 0 {
+                let _ = writeln!(out, "{path} {nanos}");
+            }
+            for &child in self.nodes[node].children.iter().rev() {
+                visit.push((child, format!("{path};{}", self.nodes[child].name)));
+            }
+        }
+        out
+    }
+
+    /// Prints the tree to the terminal.
+    pub fn print(&self, root: &str) {
+        println!("{}Profile{}", ansi(ANSI_BOLD), ansi(ANSI_RESET));
+        print!("{}", self.tree(root));
+    }
+
+    /// Writes the folded stacks to `data/profile/<day>-<root>.folded`.
+    pub fn write_folded(&self, day: Day, root: &str) -> io::Result<PathBuf> {
+        let folder = config::get().paths.data.join("profile");
+        fs::create_dir_all(&folder)?;
+        let path = folder.join(format!("{day}-{root}.folded"));
+        fs::write(&path, self.folded(root))?;
+        Ok(path)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Tree;
+    use super::{Node, Report};
+
+    fn report() -> Report {
+        let mut tree = Tree::new();
+        tree.enter("parse");
+        tree.exit(Duration::from_micros(30));
+        for _ in 0..2 {
+            tree.enter("walk");
+            tree.enter("step");
+            tree.exit(Duration::from_micros(10));
+            tree.exit(Duration::from_micros(25));
+        }
+        let mut nodes: Vec<Node> = tree.nodes;
+        nodes[0].total = Duration::from_micros(100);
+        Report { nodes }
+    }
+
+    #[test]
+    fn merges_repeated_spans() {
+        let report = report();
+        let names: Vec<_> = report.nodes.iter().map(|n| (n.name, n.calls)).collect();
+        assert_eq!(names, [("", 1), ("parse", 1), ("walk", 2), ("step", 2)]);
+        assert_eq!(report.nodes[2].total, Duration::from_micros(50));
+    }
+
+    #[test]
+    fn finds_top_level_spans() {
+        let report = report();
+        assert_eq!(report.span("parse"), Some(Duration::from_micros(30)));
+        assert_eq!(report.span("walk"), Some(Duration::from_micros(50)));
+        assert_eq!(report.span("step"), None);
+    }
+
+    #[test]
+    fn folds_self_times() {
+        assert_eq!(
+            report().folded("part1"),
+            "part1 20000\npart1;parse 30000\npart1;walk 30000\npart1;walk;step 20000\n"
+        );
+    }
+
+    #[test]
+    fn renders_tree() {
+        let tree = report().tree("part1");
+        let lines: Vec<_> = tree.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("part1 "));
+        assert!(lines[2].starts_with("  walk "));
+        assert!(lines[2].contains("50.0%"));
+        assert!(lines[3].starts_with("    step "));
+        assert!(lines[3].ends_with("×2"));
+    }
+
+    #[test]
+    fn records_nothing_when_disabled() {
+        let _span = crate::span!("ignored");
+        drop(_span);
+        assert!(super::take_report().is_none());
+    }
+}
@@ -0,0 +1,451 @@
+/// Frame recording for simulations.
+///
+/// Solutions push grid snapshots or text into a [`Recorder`]. Recording is only active when the
+/// solution runs with `--record <play|gif|frames>`, and never while benchmarking, so recording
+/// calls can stay in solution code. Once a part has run, the runner plays the frames back in the
+/// terminal or writes them to `data/viz/`.
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use std::{fs, thread};
+
+use clap::ValueEnum;
+
+use crate::grid::Grid;
+use crate::template::config;
+use crate::viz::{self, Color, Image};
+use crate::Day;
+
+/// Maps the characters of a frame to pixel colours.
+pub type Palette = fn(char) -> Color;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(vec![]);
+static PALETTE: Mutex<Option<Palette>> = Mutex::new(None);
+
+/// A recorded frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    /// Snapshot of a grid, one character per cell.
+    Grid(Grid<char>),
+    /// Free-form text, rendered as a grid of its characters when written as images.
+    Text(String),
+}
+
+impl Frame {
+    fn to_grid(&self) -> Grid<char> {
+        match self {
+            Frame::Grid(grid) => grid.clone(),
+            Frame::Text(text) => {
+                let width = text.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+                let height = text.lines().count();
+                let mut grid = Grid::new(width, height, ' ');
+                for (y, line) in text.lines().enumerate() {
+                    for (x, c) in line.chars().enumerate() {
+                        grid[(x, y)] = c;
+                    }
+                }
+                grid
+            }
+        }
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Frame::Grid(grid) => write!(f, "{grid}"),
+            Frame::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Handle solutions push frames into. All methods are no-ops unless recording was requested.
+///
+/// ```
+/// # use advent_of_code::{grid::Grid, recorder::Recorder};
+/// let mut recorder = Recorder::new();
+/// let grid = Grid::new(3, 3, '.');
+/// recorder.grid(&grid);
+/// recorder.text(|| format!("step {}", 1));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Recorder {
+    enabled: bool,
+}
+
+impl Recorder {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            enabled: ENABLED.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records a snapshot of `grid`, keeping the first character each cell displays as.
+    pub fn grid<T: Display>(&mut self, grid: &Grid<T>) {
+        if self.enabled {
+            let snapshot = grid.map(|_, cell| cell.to_string().chars().next().unwrap_or(' '));
+            push(Frame::Grid(snapshot));
+        }
+    }
+
+    /// Records a text frame. The text is only built when recording.
+    pub fn text<S: Display>(&mut self, text: impl FnOnce() -> S) {
+        if self.enabled {
+            push(Frame::Text(text().to_string()));
+        }
+    }
+
+    /// Sets the colours used when frames are written as images, see [`default_palette`].
+    pub fn palette(&mut self, palette: Palette) {
+        if self.enabled {
+            *PALETTE.lock().unwrap() = Some(palette);
+        }
+    }
+}
+
+fn push(frame: Frame) {
+    FRAMES.lock().unwrap().push(frame);
+}
+
+/// The colours used for image output unless a solution sets its own palette.
+pub fn default_palette(c: char) -> Color {
+    match c {
+        ' ' | '.' => Color::BLACK,
+        '#' => Color::WHITE,
+        '0'..='9' => {
+            let shade = 64 + (c as u8 - b'0') * 21;
+            Color::rgb(shade, shade, 255)
+        }
+        c => {
+            let hash = (c as u32).wrapping_mul(2_654_435_761);
+            Color::rgb(
+                (hash >> 16) as u8 | 64,
+                (hash >> 8) as u8 | 64,
+                hash as u8 | 64,
+            )
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// What to do with recorded frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Output {
+    /// Play the frames back in the terminal.
+    Play,
+    /// Write an animated GIF.
+    Gif,
+    /// Write a numbered series of PNG images.
+    Frames,
+}
+
+// Not part of the public API, toggled by the runner.
+#[doc(hidden)]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+// Not part of the public API, used by the runner once a part has run.
+#[doc(hidden)]
+pub fn take_frames() -> Vec<Frame> {
+    std::mem::take(&mut *FRAMES.lock().unwrap())
+}
+
+/// Plays `frames` back in the terminal at `fps` frames per second.
+pub fn play(frames: &[Frame], fps: u32) -> io::Result<()> {
+    let delay = Duration::from_secs(1) / fps.max(1);
+    let mut stdout = io::stdout();
+    for (index, frame) in frames.iter().enumerate() {
+        // clear the screen and move the cursor home.
+        write!(
+            stdout,
+            "\x1b[2J\x1b[H{frame}\n\nframe {}/{}",
+            index + 1,
+            frames.len()
+        )?;
+        stdout.flush()?;
+        thread::sleep(delay);
+    }
+    writeln!(stdout)
+}
+
+fn scale_for(frames: &[Grid<char>]) -> usize {
+    let largest = frames
+        .iter()
+        .map(|g| g.width().max(g.height()))
+        .max()
+        .unwrap_or(1);
+    (512 / largest.max(1)).clamp(1, 8)
+}
+
+fn images(frames: &[Frame]) -> Vec<Image> {
+    let palette = PALETTE.lock().unwrap().unwrap_or(default_palette);
+    let grids: Vec<_> = frames.iter().map(Frame::to_grid).collect();
+    let scale = scale_for(&grids);
+    grids
+        .iter()
+        .map(|grid| Image::from_grid(grid, scale, |_, c| palette(*c)))
+        .collect()
+}
+
+/// Writes `frames` as an animated GIF looping at `fps` frames per second.
+pub fn write_gif(frames: &[Frame], fps: u32, day: Day, name: &str) -> io::Result<PathBuf> {
+    let path = viz::output_path(day, name, "gif")?;
+    fs::write(&path, gif::encode(&images(frames), fps))?;
+    Ok(path)
+}
+
+/// Writes `frames` as numbered PNG files in `data/viz/<day>-<name>/`.
+pub fn write_frames(frames: &[Frame], day: Day, name: &str) -> io::Result<PathBuf> {
+    let folder = config::get()
+        .paths
+        .data
+        .join("viz")
+        .join(format!("{day}-{name}"));
+    fs::create_dir_all(&folder)?;
+    for (index, image) in images(frames).iter().enumerate() {
+        image.write_png(folder.join(format!("{:05}.png", index + 1)))?;
+    }
+    Ok(folder)
+}
+
+/// Animated GIF encoding with a single global palette of at most 256 colours.
+mod gif {
+    use std::collections::HashMap;
+
+    use crate::viz::{Color, Image};
+
+    const MAX_CODES: u16 = 4096;
+
+    struct BitWriter {
+        bytes: Vec<u8>,
+        current: u32,
+        bits: u8,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, code: u16, size: u8) {
+            self.current |= u32::from(code) << self.bits;
+            self.bits += size;
+            while self.bits >= 8 {
+                self.bytes.push(self.current as u8);
+                self.current >>= 8;
+                self.bits -= 8;
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.bits > 0 {
+                self.bytes.push(self.current as u8);
+            }
+            self.bytes
+        }
+    }
+
+    pub fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1_u16 << min_code_size;
+        let end = clear + 1;
+        let mut writer = BitWriter {
+            bytes: vec![],
+            current: 0,
+            bits: 0,
+        };
+        let mut size = min_code_size + 1;
+        let mut next = end + 1;
+        let mut dictionary: HashMap<(u16, u8), u16> = HashMap::new();
+
+        writer.write(clear, size);
+        let Some((&first, rest)) = indices.split_first() else {
+            writer.write(end, size);
+            return writer.finish();
+        };
+
+        let mut prefix = u16::from(first);
+        for &index in rest {
+            if let Some(&code) = dictionary.get(&(prefix, index)) {
+                prefix = code;
+                continue;
+            }
+
+            writer.write(prefix, size);
+            if next == MAX_CODES {
+                writer.write(clear, size);
+                dictionary.clear();
+                size = min_code_size + 1;
+                next = end + 1;
+            } else {
+                dictionary.insert((prefix, index), next);
+                if next == 1 << size {
+                    size += 1;
+                }
+                next += 1;
+            }
+            prefix = u16::from(index);
+        }
+
+        writer.write(prefix, size);
+        writer.write(end, size);
+        writer.finish()
+    }
+
+    fn nearest(palette: &[Color], color: Color) -> u8 {
+        let distance = |c: &Color| {
+            let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+            d(c.r, color.r) + d(c.g, color.g) + d(c.b, color.b)
+        };
+        (0..palette.len())
+            .min_by_key(|&i| distance(&palette[i]))
+            .unwrap_or(0) as u8
+    }
+
+    pub fn encode(images: &[Image], fps: u32) -> Vec<u8> {
+        let width = images.iter().map(Image::width).max().unwrap_or(1).max(1);
+        let height = images.iter().map(Image::height).max().unwrap_or(1).max(1);
+
+        // black first so that it is the background of smaller frames.
+        let mut palette = vec![Color::BLACK];
+        let mut lookup: HashMap<Color, u8> = HashMap::from([(Color::BLACK, 0)]);
+        for (_, color) in images.iter().flat_map(|image| image.pixels().iter()) {
+            if !lookup.contains_key(color) && palette.len() < 256 {
+                lookup.insert(*color, palette.len() as u8);
+                palette.push(*color);
+            }
+        }
+
+        let table_bits = (palette.len().max(2) as f64).log2().ceil() as u8;
+        let min_code_size = table_bits.max(2);
+        palette.resize(1 << table_bits, Color::BLACK);
+
+        let mut out = b"GIF89a".to_vec();
+        out.extend((width as u16).to_le_bytes());
+        out.extend((height as u16).to_le_bytes());
+        out.push(0x80 | ((table_bits - 1) << 4) | (table_bits - 1));
+        out.extend([0, 0]);
+        for color in &palette {
+            out.extend([color.r, color.g, color.b]);
+        }
+
+        // loop forever.
+        out.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        let delay = (100 / fps.max(1)).max(2) as u16;
+        for image in images {
+            let mut indices = vec![0; width * height];
+            for ((x, y), color) in image.pixels().iter() {
+                indices[y * width + x] = match lookup.get(color) {
+                    Some(index) => *index,
+                    None => nearest(&palette, *color),
+                };
+            }
+
+            out.extend([0x21, 0xf9, 0x04, 0x00]);
+            out.extend(delay.to_le_bytes());
+            out.extend([0x00, 0x00]);
+
+            out.push(0x2c);
+            out.extend([0, 0, 0, 0]);
+            out.extend((width as u16).to_le_bytes());
+            out.extend((height as u16).to_le_bytes());
+            out.push(0);
+
+            out.push(min_code_size);
+            for block in lzw(&indices, min_code_size).chunks(255) {
+                out.push(block.len() as u8);
+                out.extend(block);
+            }
+            out.push(0);
+        }
+
+        out.push(0x3b);
+        out
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{gif, Frame};
+
+    /// Reference LZW decoder, following the GIF89a specification.
+    fn decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1_usize << min_code_size;
+        let initial: Vec<Vec<u8>> = (0..clear + 2).map(|i| vec![i as u8]).collect();
+        let mut table = initial.clone();
+        let mut size = min_code_size + 1;
+        let mut out = vec![];
+        let mut prev: Option<usize> = None;
+        let (mut bits, mut acc, mut pos) = (0, 0_u32, 0);
+
+        loop {
+            while bits < size {
+                acc |= u32::from(data[pos]) << bits;
+                pos += 1;
+                bits += 8;
+            }
+            let code = (acc & ((1 << size) - 1)) as usize;
+            acc >>= size;
+            bits -= size;
+
+            if code == clear {
+                table = initial.clone();
+                size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return out;
+            }
+
+            let entry = match (table.get(code), prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(p)) => [table[p].clone(), vec![table[p][0]]].concat(),
+                (None, None) => panic!("invalid code"),
+            };
+            out.extend(&entry);
+            if let Some(p) = prev {
+                if table.len() < 4096 {
+                    table.push([table[p].clone(), vec![entry[0]]].concat());
+                }
+            }
+            prev = Some(code);
+            if table.len() == 1 << size && size < 12 {
+                size += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let mut seed = 7_u32;
+        let indices: Vec<u8> = (0..20_000)
+            .map(|i| {
+                seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                if i % 3 == 0 {
+                    (seed >> 16) as u8 % 5
+                } else {
+                    1
+                }
+            })
+            .collect();
+        assert_eq!(decode(&gif::lzw(&indices, 3), 3), indices);
+        assert_eq!(decode(&gif::lzw(&[], 2), 2), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn text_frames_become_grids() {
+        let grid = Frame::Text("ab\nc".into()).to_grid();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[(1, 1)], ' ');
+    }
+}
@@ -0,0 +1,102 @@
+/// Local store of accepted answers, one file per day in `data/answers/`.
+///
+/// A file holds the answer of part one on its first line and the answer of part two on its
+/// second line, an empty line stands for a part that is not solved yet. Answers are recorded by
+/// hand once the website accepted them; the runner then checks results against them and the
+/// readme star table counts them.
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::template::config;
+use crate::Day;
+
+/// Recorded answers of both parts of a day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Parses the content of an answer file.
+    pub fn parse(s: &str) -> Self {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .map(|line| (!line.is_empty()).then(|| line.to_string()));
+        Self {
+            part_1: lines.next().flatten(),
+            part_2: lines.next().flatten(),
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+pub fn get_path(day: Day) -> PathBuf {
+    config::get()
+        .paths
+        .data
+        .join("answers")
+        .join(format!("{day}.txt"))
+}
+
+/// Reads the recorded answers of a day, both parts are [`None`] if there is no answer file.
+pub fn read(day: Day) -> io::Result<Answers> {
+    match fs::read_to_string(get_path(day)) {
+        Ok(content) => Ok(Answers::parse(&content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Outcome of comparing a result with the recorded answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// No answer is recorded for the part.
+    Unknown,
+    Correct,
+    /// The result differs from the recorded answer, which is included.
+    Wrong(String),
+}
+
+/// Compares a result with the recorded answer of a part.
+pub fn verify(answers: &Answers, part: u8, result: &str) -> Verdict {
+    match answers.part(part) {
+        None => Verdict::Unknown,
+        Some(answer) if answer == result.trim() => Verdict::Correct,
+        Some(answer) => Verdict::Wrong(answer.to_string()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verify, Answers, Verdict};
+
+    #[test]
+    fn parses_answer_files() {
+        assert_eq!(Answers::parse(""), Answers::default());
+        let answers = Answers::parse("142\n");
+        assert_eq!(answers.part(1), Some("142"));
+        assert_eq!(answers.part(2), None);
+        let answers = Answers::parse("\n 281 \r\n");
+        assert_eq!(answers.part(1), None);
+        assert_eq!(answers.part(2), Some("281"));
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = Answers::parse("142\n");
+        assert_eq!(verify(&answers, 1, "142"), Verdict::Correct);
+        assert_eq!(verify(&answers, 1, "143"), Verdict::Wrong("142".into()));
+        assert_eq!(verify(&answers, 2, "281"), Verdict::Unknown);
+    }
+}
@@ -0,0 +1,86 @@
+/// Minimal client for the Advent of Code website, for what aoc-cli does not cover.
+/// Requests are made with `curl`, authenticated with the session cookie aoc-cli uses as well.
+use std::fmt::Display;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+use crate::template::config;
+
+const BASE_URL: &str = "https://adventofcode.com";
+/// Identifies the tool to the website, as requested by its maintainer.
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum ClientError {
+    SessionNotFound,
+    CurlNotFound,
+    /// The request failed, with the error reported by curl.
+    Request(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found, set ADVENT_OF_CODE_SESSION, paths.session or create ~/.adventofcode.session."
+            ),
+            ClientError::CurlNotFound => write!(f, "curl is not present in environment."),
+            ClientError::Request(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+/// Reads the session cookie from `ADVENT_OF_CODE_SESSION`, the file configured in `paths.session`
+/// or `~/.adventofcode.session`.
+pub fn session() -> Result<String, ClientError> {
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+    let path = match &config::get().paths.session {
+        Some(path) => path.clone(),
+        None => env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+            .ok_or(ClientError::SessionNotFound)?,
+    };
+    fs::read_to_string(path)
+        .map(|s| s.trim().to_string())
+        .map_err(|_| ClientError::SessionNotFound)
+}
+
+/// Fetches `path`, e.g. `/2023/day/1`, and returns the body of the response.
+pub fn get(path: &str) -> Result<String, ClientError> {
+    let session = session()?;
+
+    // the cookie is passed as curl config on stdin, to keep it out of the process list.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--config", "-"])
+        .arg(format!("{BASE_URL}{path}"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| ClientError::CurlNotFound)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "cookie = \"session={session}\"")
+            .map_err(|e| ClientError::Request(e.to_string()))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| ClientError::Request(e.to_string()))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(ClientError::Request(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
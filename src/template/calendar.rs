@@ -0,0 +1,163 @@
+/// Date and time helpers for puzzle unlock times and timestamps of the Advent of Code API.
+/// Puzzles unlock at midnight EST (UTC-5), i.e. at 05:00 UTC on the first 25 days of December.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{all_days, Day};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+const UNLOCK_HOUR_UTC: i64 = 5;
+
+/// Seconds since the unix epoch.
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Days since the unix epoch of a date, see <https://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date of a number of days since the unix epoch, as `(year, month, day)`.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Unix timestamp of the moment the puzzle of `day` unlocks in `year`.
+pub fn unlock_time(year: u16, day: Day) -> i64 {
+    days_from_civil(i64::from(year), 12, i64::from(day.into_inner())) * SECONDS_PER_DAY
+        + UNLOCK_HOUR_UTC * 60 * 60
+}
+
+/// The first day of `year` that unlocks after `now`, if any.
+pub fn next_unlock(year: u16, now: i64) -> Option<Day> {
+    all_days().find(|&day| unlock_time(year, day) > now)
+}
+
+/// The last day of `year` that unlocked at or before `now`, if any.
+pub fn latest_unlock(year: u16, now: i64) -> Option<Day> {
+    all_days()
+        .take_while(|&day| unlock_time(year, day) <= now)
+        .last()
+}
+
+/// The year of the latest event that started at `timestamp`, i.e. the previous year before December.
+pub fn event_year(timestamp: i64) -> u16 {
+    let (year, month, _) =
+        civil_from_days((timestamp - UNLOCK_HOUR_UTC * 60 * 60).div_euclid(SECONDS_PER_DAY));
+    let year = if month < 12 { year - 1 } else { year };
+    year as u16
+}
+
+/// Formats a unix timestamp as `2023-12-01 05:00:00 UTC`.
+pub fn format_timestamp(timestamp: i64) -> String {
+    let (year, month, day) = civil_from_days(timestamp.div_euclid(SECONDS_PER_DAY));
+    let seconds = timestamp.rem_euclid(SECONDS_PER_DAY);
+    format!(
+        "{year}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Formats a number of seconds as `HH:MM:SS`, with days prepended if needed, e.g. `2d 01:02:03`.
+pub fn format_duration(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.abs();
+    let days = seconds / SECONDS_PER_DAY;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{sign}{days}d {time}")
+    } else {
+        format!("{sign}{time}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        civil_from_days, event_year, format_duration, format_timestamp, latest_unlock, next_unlock,
+        unlock_time,
+    };
+    use crate::day;
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19692), (2023, 12, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        assert_eq!(unlock_time(2023, day!(1)), 1_701_406_800);
+        assert_eq!(
+            format_timestamp(unlock_time(2015, day!(25))),
+            "2015-12-25 05:00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn computes_event_years() {
+        assert_eq!(event_year(unlock_time(2023, day!(1))), 2023);
+        assert_eq!(event_year(unlock_time(2023, day!(1)) - 1), 2022);
+        assert_eq!(event_year(unlock_time(2023, day!(25)) + 40 * 86400), 2023);
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(next_unlock(2023, 0), Some(day!(1)));
+        assert_eq!(next_unlock(2023, unlock_time(2023, day!(5))), Some(day!(6)));
+        assert_eq!(next_unlock(2023, unlock_time(2023, day!(25))), None);
+    }
+
+    #[test]
+    fn finds_latest_unlock() {
+        assert_eq!(latest_unlock(2023, 0), None);
+        assert_eq!(
+            latest_unlock(2023, unlock_time(2023, day!(5))),
+            Some(day!(5))
+        );
+        assert_eq!(
+            latest_unlock(2023, unlock_time(2023, day!(6)) - 1),
+            Some(day!(5))
+        );
+        assert_eq!(
+            latest_unlock(2023, unlock_time(2024, day!(1))),
+            Some(day!(25))
+        );
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(0), "00:00:00");
+        assert_eq!(format_duration(3723), "01:02:03");
+        assert_eq!(format_duration(2 * 86400 + 3723), "2d 01:02:03");
+        assert_eq!(format_duration(-61), "-00:01:01");
+    }
+}
@@ -0,0 +1,252 @@
+/// Builds the solution binaries once with cargo, then runs the built executables directly.
+///
+/// `cargo run` checks whether the build is fresh on every invocation and mixes its own output into
+/// the output of the solution. Instead, the binaries of all selected days are built by a single
+/// `cargo build --message-format=json`, whose messages tell where the executables are. Compiler
+/// diagnostics are taken from these messages and printed to stderr, stdout of the commands only
+/// carries the output of the solutions.
+use std::fmt::Display;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+
+use serde::Deserialize;
+
+use crate::template::{cli, config};
+use crate::{Day, DaySet};
+
+/// How the solutions are built.
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    pub release: bool,
+    /// Cargo features to build with, e.g. `mmap` or `count_allocations`.
+    pub features: Option<String>,
+    /// Builds the test harnesses of the solutions instead, like `cargo test --no-run`.
+    pub tests: bool,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// cargo could not be started.
+    Spawn(io::Error),
+    /// A message of cargo could not be read.
+    Message(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Spawn(e) => write!(f, "could not run cargo: {e}"),
+            Error::Message(message) => write!(f, "unexpected message from cargo: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A message of cargo, with the fields that are used.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum CargoMessage {
+    CompilerMessage {
+        message: Diagnostic,
+    },
+    CompilerArtifact(Artifact),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct Diagnostic {
+    rendered: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Artifact {
+    target: Target,
+    profile: Profile,
+    /// Path of the built executable, [`None`] for libraries.
+    executable: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Target {
+    kind: Vec<String>,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Profile {
+    test: bool,
+}
+
+/// Executables of the solutions that were built.
+#[derive(Debug, Default)]
+pub struct Artifacts {
+    executables: Vec<(Day, PathBuf)>,
+}
+
+impl Artifacts {
+    /// The executable of a day, [`None`] if it failed to build.
+    pub fn get(&self, day: Day) -> Option<&Path> {
+        self.executables
+            .iter()
+            .find(|(d, _)| *d == day)
+            .map(|(_, path)| path.as_path())
+    }
+}
+
+/// Builds the solutions of `days` with a single cargo invocation.
+///
+/// The build keeps going when a solution fails to compile, so the other days can still run;
+/// the failing days have no executable in the returned [`Artifacts`].
+pub fn build(days: DaySet, options: &BuildOptions) -> Result<Artifacts, Error> {
+    let mut artifacts = Artifacts::default();
+    if days.is_empty() {
+        return Ok(artifacts);
+    }
+
+    let format = if config::get().output.color.enabled() {
+        "json-diagnostic-rendered-ansi"
+    } else {
+        "json"
+    };
+    let mut args = if options.tests {
+        vec![
+            "test".to_string(),
+            "--no-run".to_string(),
+            "--no-fail-fast".to_string(),
+        ]
+    } else {
+        vec!["build".to_string(), "--keep-going".to_string()]
+    };
+    args.push(format!("--message-format={format}"));
+    for day in days {
+        args.push("--bin".to_string());
+        args.push(day.to_string());
+    }
+    if options.release {
+        args.push("--release".to_string());
+    }
+    if let Some(features) = &options.features {
+        args.push("--features".to_string());
+        args.push(features.clone());
+    }
+
+    // cargo reports its progress on stderr, the messages are read from stdout.
+    let mut child = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(Error::Spawn)?;
+    let stdout = child.stdout.take().expect("stdout of cargo is piped");
+
+    for line in BufReader::new(stdout).lines() {
+        let line = line.map_err(Error::Spawn)?;
+        let message = serde_json::from_str(&line).map_err(|_| Error::Message(line.clone()))?;
+        match message {
+            CargoMessage::CompilerMessage { message } => {
+                if let Some(rendered) = message.rendered {
+                    eprint!("{rendered}");
+                }
+            }
+            CargoMessage::CompilerArtifact(artifact) => {
+                if let Some(artifact) = parse_artifact(artifact, options.tests) {
+                    artifacts.executables.push(artifact);
+                }
+            }
+            CargoMessage::Other => {}
+        }
+    }
+
+    // a failed build is reported by cargo, the days without executables tell which ones failed.
+    child.wait().map_err(Error::Spawn)?;
+    Ok(artifacts)
+}
+
+/// Reads the day and executable of a `compiler-artifact` message of a solution binary, or of its
+/// test harness if `tests` is set.
+fn parse_artifact(artifact: Artifact, tests: bool) -> Option<(Day, PathBuf)> {
+    if artifact.profile.test != tests || !artifact.target.kind.iter().any(|kind| kind == "bin") {
+        return None;
+    }
+    let day = artifact.target.name.parse().ok()?;
+    Some((day, artifact.executable?))
+}
+
+/// Prepares running a built solution with `args`. The solution gets the colour setting, as it
+/// cannot tell whether its output ends up in a terminal if the output is piped.
+pub fn command(executable: &Path, args: &[String]) -> Command {
+    let color = if config::get().output.color.enabled() {
+        "always"
+    } else {
+        "never"
+    };
+    let mut command = Command::new(executable);
+    command
+        .args(args)
+        .env(config::env_var("output.color"), color);
+    command
+}
+
+/// Exit code of a finished solution, [`cli::EXIT_FAILURE`] if it was terminated by a signal.
+pub fn exit_code(status: ExitStatus) -> i32 {
+    status.code().unwrap_or(cli::EXIT_FAILURE)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse_artifact, CargoMessage};
+    use crate::day;
+
+    #[test]
+    fn parses_artifacts() {
+        let message = |name: &str, kind: &str, executable: &str, test: bool| {
+            match serde_json::from_str(&format!(
+                r#"{{"reason":"compiler-artifact","target":{{"kind":["{kind}"],"name":"{name}"}},"profile":{{"test":{test}}},"executable":{executable},"fresh":true}}"#
+            ))
+            .unwrap()
+            {
+                CargoMessage::CompilerArtifact(artifact) => artifact,
+                message => panic!("unexpected message {message:?}"),
+            }
+        };
+
+        assert_eq!(
+            parse_artifact(message("03", "bin", "\"/target/debug/03\"", false), false),
+            Some((day!(3), PathBuf::from("/target/debug/03")))
+        );
+        assert_eq!(
+            parse_artifact(
+                message("03", "bin", "\"/target/debug/deps/03-1f\"", true),
+                true
+            ),
+            Some((day!(3), PathBuf::from("/target/debug/deps/03-1f")))
+        );
+        assert_eq!(
+            parse_artifact(message("03", "bin", "\"/target/debug/03\"", false), true),
+            None
+        );
+        assert_eq!(
+            parse_artifact(message("advent_of_code", "lib", "null", false), false),
+            None
+        );
+        assert_eq!(
+            parse_artifact(
+                message(
+                    "advent_of_code",
+                    "bin",
+                    "\"/target/debug/advent_of_code\"",
+                    false
+                ),
+                false
+            ),
+            None
+        );
+    }
+}
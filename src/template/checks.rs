@@ -0,0 +1,118 @@
+/// Outcome of the last example tests and verification of every day, one file per day in
+/// `data/checks/`.
+///
+/// The `test` and `verify` commands record the outcome of every day they check, so the dashboard
+/// can show it without running anything. A file holds the outcome of the example tests on its
+/// first line and the outcome of the verification on its second line, either `passed` or
+/// `failed`; an empty line stands for a check that did not run yet or had nothing to check.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::template::config;
+use crate::Day;
+
+/// Outcome of a check.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Check {
+    #[default]
+    Unknown,
+    Passed,
+    Failed,
+}
+
+impl Check {
+    fn parse(s: &str) -> Self {
+        match s.trim() {
+            "passed" => Check::Passed,
+            "failed" => Check::Failed,
+            _ => Check::Unknown,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Check::Unknown => "",
+            Check::Passed => "passed",
+            Check::Failed => "failed",
+        }
+    }
+}
+
+/// Recorded checks of a day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Checks {
+    /// Outcome of the last `test` run.
+    pub examples: Check,
+    /// Outcome of the last `verify` run.
+    pub answers: Check,
+}
+
+impl Checks {
+    /// Parses the content of a check file.
+    pub fn parse(s: &str) -> Self {
+        let mut lines = s.lines().map(Check::parse);
+        Self {
+            examples: lines.next().unwrap_or_default(),
+            answers: lines.next().unwrap_or_default(),
+        }
+    }
+}
+
+impl Display for Checks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.examples.name())?;
+        writeln!(f, "{}", self.answers.name())
+    }
+}
+
+pub fn get_path(day: Day) -> PathBuf {
+    config::get()
+        .paths
+        .data
+        .join("checks")
+        .join(format!("{day}.txt"))
+}
+
+/// Reads the recorded checks of a day, both are [`Check::Unknown`] if there is no check file.
+pub fn read(day: Day) -> io::Result<Checks> {
+    match fs::read_to_string(get_path(day)) {
+        Ok(content) => Ok(Checks::parse(&content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Checks::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Updates the recorded checks of a day with `f`.
+pub fn record(day: Day, f: impl FnOnce(&mut Checks)) -> io::Result<()> {
+    let mut checks = read(day)?;
+    f(&mut checks);
+    let path = get_path(day);
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    fs::write(path, checks.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Check, Checks};
+
+    #[test]
+    fn reads_what_it_writes() {
+        assert_eq!(Checks::parse(""), Checks::default());
+        let checks = Checks {
+            examples: Check::Failed,
+            answers: Check::Passed,
+        };
+        assert_eq!(checks.to_string(), "failed\npassed\n");
+        assert_eq!(Checks::parse(&checks.to_string()), checks);
+        let checks = Checks {
+            examples: Check::Unknown,
+            answers: Check::Passed,
+        };
+        assert_eq!(Checks::parse(&checks.to_string()), checks);
+    }
+}
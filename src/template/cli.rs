@@ -0,0 +1,323 @@
+/// Command-line arguments of the `advent_of_code` tool and the solution binaries, declared with
+/// clap. Parsing, validation, `--help` and the shell completions are all derived from these types.
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+use crate::recorder;
+use crate::template::commands::{leaderboard, solve};
+use crate::template::readme_benchmarks::{Column, Format, Sort, TableOptions};
+use crate::{Day, DaySelection};
+
+/// Exit code of commands that failed.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code of invalid arguments or configuration.
+pub const EXIT_USAGE: i32 = 2;
+
+/// Scaffolds, runs, benchmarks and submits Advent of Code solutions.
+#[derive(Debug, Parser)]
+#[command(name = "advent_of_code")]
+pub struct App {
+    /// Overrides a setting of aoc.toml, can be repeated.
+    #[arg(long, value_name = "KEY=VALUE", global = true)]
+    pub set: Vec<String>,
+    #[command(subcommand)]
+    pub command: AppCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AppCommand {
+    /// Runs all solutions, optionally benchmarks them and updates the readme.
+    All {
+        /// Days to run, all by default.
+        days: Option<DaySelection>,
+        /// Builds the solutions in release mode.
+        #[arg(long)]
+        release: bool,
+        /// Benchmarks both parts.
+        #[arg(long)]
+        time: bool,
+        #[command(flatten)]
+        table: TableFlags,
+    },
+    /// Prints a shell completion script.
+    Completions {
+        /// Shell to complete.
+        shell: Shell,
+    },
+    /// Shows the effective configuration.
+    Config {
+        /// What to do, `show` by default.
+        #[arg(value_parser = ["show"])]
+        action: Option<String>,
+    },
+    /// Shows the state of every day and runs actions on them.
+    Dashboard,
+    /// Downloads the inputs and descriptions of days with aoc-cli.
+    Download {
+        /// Days of the puzzles, e.g. 3, 1-5, 1,4,9, latest, unsolved or all.
+        days: DaySelection,
+    },
+    /// Fuzzes all scaffolded days and reports which ones are panic-free.
+    Fuzz {
+        /// Builds the solutions in release mode.
+        #[arg(long)]
+        release: bool,
+        /// Inputs generated per part.
+        #[arg(long, value_name = "N")]
+        iterations: Option<usize>,
+    },
+    /// Shows a private leaderboard, your own if no id is passed.
+    Leaderboard {
+        /// Id of the leaderboard.
+        id: Option<u64>,
+        #[command(flatten)]
+        options: leaderboard::Options,
+    },
+    /// Shows the puzzle description of a day.
+    Read {
+        /// Day of the puzzle, 1 to 25.
+        day: Day,
+        /// Only reads the cached description.
+        #[arg(long)]
+        offline: bool,
+    },
+    /// Creates the solution files and empty input files of days.
+    Scaffold {
+        /// Days of the puzzles, e.g. 3, 1-5, 1,4,9, latest, unsolved or all.
+        days: DaySelection,
+    },
+    /// Runs the solutions of days.
+    Solve {
+        /// Days of the puzzles, e.g. 3, 1-5, 1,4,9, latest, unsolved or all.
+        days: DaySelection,
+        #[command(flatten)]
+        options: solve::Options,
+    },
+    /// Updates the star table of the readme.
+    Stars {
+        /// Private leaderboard JSON to read stars from.
+        #[arg(long, value_name = "FILE")]
+        leaderboard: Option<PathBuf>,
+        /// Member of the leaderboard, AOC_USER_ID by default.
+        #[arg(long = "user", value_name = "ID")]
+        user_id: Option<u64>,
+    },
+    /// Shows personal statistics per day.
+    Stats {
+        /// Only shows one year.
+        #[arg(long)]
+        year: Option<u16>,
+    },
+    /// Runs the example tests of solutions and sums them up per part.
+    Test {
+        /// Days to test, all by default.
+        days: Option<DaySelection>,
+    },
+    /// Checks the results of solutions against the recorded answers.
+    Verify {
+        /// Days to check, all by default.
+        days: Option<DaySelection>,
+    },
+    /// Waits for a puzzle to unlock, then scaffolds and downloads it.
+    Wait {
+        /// Day to wait for, the next one by default.
+        day: Option<Day>,
+    },
+}
+
+/// Flags of `all` that lay out the benchmark table, they override `[bench]` of aoc.toml.
+#[derive(Debug, Args)]
+pub struct TableFlags {
+    /// Adds columns to the benchmark table.
+    #[arg(long, value_name = "LIST", value_delimiter = ',')]
+    columns: Option<Vec<Column>>,
+    /// Orders the benchmark table.
+    #[arg(long, value_name = "ORDER")]
+    sort: Option<Sort>,
+    /// Adds bars showing the cost of each day.
+    #[arg(long)]
+    bars: bool,
+    /// Keeps one benchmark table per year in the readme.
+    #[arg(long)]
+    by_year: bool,
+    /// Also writes the benchmarks to data/benchmarks.<format>.
+    #[arg(long, value_name = "LIST", value_delimiter = ',')]
+    format: Option<Vec<Format>>,
+}
+
+impl TableFlags {
+    /// The table options, with the ones that were not given taken from `defaults`.
+    pub fn resolve(self, defaults: TableOptions) -> TableOptions {
+        TableOptions {
+            columns: self.columns.unwrap_or(defaults.columns),
+            sort: self.sort.unwrap_or(defaults.sort),
+            bars: self.bars || defaults.bars,
+            by_year: self.by_year || defaults.by_year,
+            formats: self.format.unwrap_or(defaults.formats),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Arguments of the solution binaries, usually run through `cargo solve <day>`.
+#[derive(Debug, Parser)]
+#[command(about = "Runs both parts of a day on its input")]
+pub struct Solution {
+    #[command(flatten)]
+    pub flags: SolutionFlags,
+    /// Fuzzes both parts with arbitrary input instead of running them.
+    #[arg(long, value_name = "ITERATIONS")]
+    pub fuzz: Option<Option<usize>>,
+    /// Prints the duration of the `parse` span of the first run, for the parse column of `all`.
+    #[arg(long, hide = true)]
+    pub parse_time: bool,
+}
+
+/// Flags of the solution binaries. `solve` accepts them too and passes them on.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Args)]
+pub struct SolutionFlags {
+    /// Submits the answer of a part.
+    #[arg(long = "submit", value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub submit_part: Option<u8>,
+    /// Benchmarks both parts.
+    #[arg(long)]
+    pub time: bool,
+    /// Prints the visualisations of the first run.
+    #[arg(long)]
+    pub viz: bool,
+    /// Plays back the recorded frames, or writes them as a GIF or PNG files.
+    #[arg(long, value_name = "OUTPUT")]
+    pub record: Option<recorder::Output>,
+    /// Frame rate of recordings, 10 by default.
+    #[arg(long, value_name = "N")]
+    pub fps: Option<u32>,
+    /// Prints the spans of the first run and writes folded stacks.
+    #[arg(long)]
+    pub profile: bool,
+}
+
+impl SolutionFlags {
+    /// The flags as arguments of a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(submit_part) = self.submit_part {
+            args.push("--submit".to_string());
+            args.push(submit_part.to_string());
+        }
+
+        if self.time {
+            args.push("--time".to_string());
+        }
+
+        if self.viz {
+            args.push("--viz".to_string());
+        }
+
+        if let Some(record) = self.record.and_then(|record| record.to_possible_value()) {
+            args.push("--record".to_string());
+            args.push(record.get_name().to_string());
+        }
+
+        if let Some(fps) = self.fps {
+            args.push("--fps".to_string());
+            args.push(fps.to_string());
+        }
+
+        if self.profile {
+            args.push("--profile".to_string());
+        }
+
+        args
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use clap::error::ErrorKind;
+    use clap::{CommandFactory, Parser};
+
+    use super::{App, AppCommand, Solution, SolutionFlags};
+    use crate::recorder;
+    use crate::template::readme_benchmarks::{Column, Sort, TableOptions};
+
+    fn parse(args: &str) -> Result<App, clap::Error> {
+        App::try_parse_from(
+            ["advent_of_code"]
+                .into_iter()
+                .chain(args.split_whitespace()),
+        )
+    }
+
+    #[test]
+    fn models_are_valid() {
+        App::command().debug_assert();
+        Solution::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_arguments() {
+        let app = parse("solve 3 --time --submit=2 --set a=1 --set b=2").unwrap();
+        assert_eq!(app.set, vec!["a=1", "b=2"]);
+        let AppCommand::Solve { days, options } = app.command else {
+            panic!("expected solve");
+        };
+        assert_eq!(days.to_string(), "3");
+        assert!(options.solution.time);
+        assert_eq!(options.solution.submit_part, Some(2));
+
+        let app = parse("all --columns samples,allocs --sort slowest").unwrap();
+        let AppCommand::All {
+            days: None, table, ..
+        } = app.command
+        else {
+            panic!("expected all");
+        };
+        let table = table.resolve(TableOptions::default());
+        assert_eq!(table.columns, vec![Column::Samples, Column::Allocations]);
+        assert_eq!(table.sort, Sort::Slowest);
+        assert!(table.formats.is_empty());
+
+        let app = parse("wait").unwrap();
+        assert!(matches!(app.command, AppCommand::Wait { day: None }));
+
+        let solution = Solution::try_parse_from(["01", "--fuzz", "--time"]).unwrap();
+        assert_eq!(solution.fuzz, Some(None));
+        assert!(solution.flags.time);
+        let solution = Solution::try_parse_from(["01", "--fuzz", "20"]).unwrap();
+        assert_eq!(solution.fuzz, Some(Some(20)));
+    }
+
+    #[test]
+    fn reports_errors() {
+        let kind = |args: &str| parse(args).unwrap_err().kind();
+        assert_eq!(kind("solv 1"), ErrorKind::InvalidSubcommand);
+        assert_eq!(kind("solve 1 --tiem"), ErrorKind::UnknownArgument);
+        assert_eq!(kind("solve 1 --submit 3"), ErrorKind::ValueValidation);
+        assert_eq!(kind("solve 1 --record gfi"), ErrorKind::InvalidValue);
+        assert_eq!(kind("solve"), ErrorKind::MissingRequiredArgument);
+        assert_eq!(kind("solve x"), ErrorKind::ValueValidation);
+        assert_eq!(kind("--help"), ErrorKind::DisplayHelp);
+        assert_eq!(parse("solve 1 --tiem").unwrap_err().exit_code(), 2);
+    }
+
+    #[test]
+    fn mirrors_solution_flags() {
+        let flags = SolutionFlags {
+            submit_part: Some(1),
+            time: true,
+            viz: true,
+            record: Some(recorder::Output::Gif),
+            fps: Some(30),
+            profile: true,
+        };
+        let args = flags.to_args();
+        let solution = Solution::try_parse_from(["01".to_string()].into_iter().chain(args));
+        assert_eq!(solution.unwrap().flags, flags);
+    }
+}
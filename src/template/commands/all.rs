@@ -1,39 +1,82 @@
-use std::io;
+use std::fmt::Display;
+use std::{io, process};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    ansi, cargo, cli, is_scaffolded,
+    readme_benchmarks::{self, TableOptions, Timings},
+    select_days, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{DaySelection, DaySet};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(selection: DaySelection, is_release: bool, is_timed: bool, table: &TableOptions) {
     let mut timings: Vec<Timings> = vec![];
+    let (bold, italic, reset) = (ansi(ANSI_BOLD), ansi(ANSI_ITALIC), ansi(ANSI_RESET));
+
+    let days = select_days(selection);
+    // days that have not been scaffolded yet are not built.
+    let scaffolded: DaySet = days.iter().filter(|&day| is_scaffolded(day)).collect();
+    let build_options = cargo::BuildOptions {
+        release: is_release,
+        // mirror allocation counting, the feature has to be enabled in the child build.
+        features: crate::allocations::enabled().then(|| "count_allocations".to_string()),
+        tests: false,
+    };
+    let artifacts = cargo::build(scaffolded, &build_options).unwrap_or_else(|e| {
+        eprintln!("Failed to build the solutions: {e}");
+        process::exit(cli::EXIT_FAILURE);
+    });
+    let mut exit_code = 0;
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    days.iter().enumerate().for_each(|(i, day)| {
+        if i > 0 {
             println!();
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("{bold}Day {day}{reset}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-        if output.is_empty() {
+        if !scaffolded.contains(day) {
             println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            return;
+        }
+        let Some(executable) = artifacts.get(day) else {
+            eprintln!("Day {day} failed to build.");
+            exit_code = exit_code.max(cli::EXIT_FAILURE);
+            return;
+        };
+
+        match child_commands::run_solution(executable, is_timed, table.needs_parse_time()) {
+            Ok((output, status)) => {
+                exit_code = exit_code.max(cargo::exit_code(status));
+                if output.is_empty() {
+                    println!("Not solved.");
+                } else {
+                    timings.push(child_commands::parse_exec_time(&output, day));
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to run day {day}: {e}");
+                exit_code = exit_code.max(cli::EXIT_FAILURE);
+            }
         }
     });
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
-
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+        println!("\n{bold}Total:{reset} {italic}{total_millis:.2}ms{reset}");
+
+        // the readme table and exports cover every day, a subset would drop the others.
+        if is_release && selection != DaySelection::All {
+            println!("Benchmarks of `{selection}` are not written to the readme, select all days to update it.");
+        } else if is_release {
+            match readme_benchmarks::write_outputs(&timings, total_millis, table) {
+                Ok(paths) => paths
+                    .iter()
+                    .for_each(|path| println!("Wrote benchmarks to {}.", path.display())),
+                Err(_) => eprintln!("Failed to write benchmark files."),
+            }
+            match readme_benchmarks::update(timings, total_millis, table) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -41,6 +84,10 @@ pub fn handle(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    if exit_code != 0 {
+        process::exit(exit_code);
+    }
 }
 
 #[derive(Debug)]
@@ -50,54 +97,59 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "output of the solution is not piped"),
+            Error::Parser(message) => write!(f, "{message}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+pub use readme_benchmarks::get_path_for_bin;
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::Error;
+    use crate::template::cargo;
+    use crate::template::readme_benchmarks::PartTiming;
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{ExitStatus, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+    /// Run the built solution of a day, returns the lines it printed and how it exited.
+    pub fn run_solution(
+        executable: &Path,
+        is_timed: bool,
+        is_parse_timed: bool,
+    ) -> Result<(Vec<String>, ExitStatus), Error> {
+        let mut args = vec![];
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".to_string());
+        }
+
+        if is_parse_timed {
+            // the parse span is needed for the parse column.
+            args.push("--parse-time".to_string());
         }
 
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = cargo::command(executable, &args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -120,9 +172,9 @@ mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
 
-        Ok(output)
+        Ok((output, status))
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -133,30 +185,44 @@ mod child_commands {
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        // parse times are printed after the result line of their part.
+        let mut current_part = None;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+        for l in output {
+            if let Some(parse) = parse_parse_time(l) {
+                let part = match current_part {
+                    Some(1) => timings.part_1.as_mut(),
+                    Some(2) => timings.part_2.as_mut(),
+                    _ => None,
                 };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                if let Some(part) = part {
+                    part.parse = Some(parse.into());
                 }
+                continue;
+            }
 
-                timings.total_nanos += nanos;
-            });
+            if !l.contains(" samples)") {
+                continue;
+            }
+
+            let Some(part_timing) = parse_part_timing(l) else {
+                eprintln!("Could not parse timings from line: {l}");
+                continue;
+            };
+
+            let Some(part) = l.split(':').next() else {
+                continue;
+            };
+
+            timings.total_nanos += part_timing.nanos;
+            if part.contains("Part 1") {
+                timings.part_1 = Some(part_timing);
+                current_part = Some(1);
+            } else if part.contains("Part 2") {
+                timings.part_2 = Some(part_timing);
+                current_part = Some(2);
+            }
+        }
 
         timings
     }
@@ -165,25 +231,44 @@ mod child_commands {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    fn parse_nanos(s: &str) -> Option<f64> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        }
+    }
+
+    /// Parses a timing like `(74.13ns ±1.2ns @ 100000 samples) [3 allocs, 1.5 KiB peak]`.
+    fn parse_part_timing(line: &str) -> Option<PartTiming> {
+        let (head, tail) = line.rsplit_once(" samples)")?;
+        let (timing, samples) = head.rsplit_once('(')?.1.split_once('@')?;
+        let (time, stddev) = match timing.split_once('±') {
+            Some((time, stddev)) => (time.trim(), Some(stddev.trim())),
+            None => (timing.trim(), None),
+        };
 
-        Some((str_timing, parsed_timing))
+        Some(PartTiming {
+            time: time.into(),
+            nanos: parse_nanos(time)?,
+            samples: samples.trim().parse().ok(),
+            stddev: stddev.map(Into::into),
+            allocations: parse_allocations(tail).map(Into::into),
+            parse: None,
+        })
+    }
+
+    /// Parses the allocation stats printed after the timing, e.g. `[12 allocs, 1.5 KiB peak]`.
+    fn parse_allocations(tail: &str) -> Option<&str> {
+        tail.trim().strip_prefix('[')?.strip_suffix(']')
+    }
+
+    /// Parses the duration of the parse span printed with `--parse-time`, e.g. `        parse: 1.2ms`.
+    fn parse_parse_time(line: &str) -> Option<&str> {
+        let duration = line.strip_prefix("        parse: ")?.trim_end();
+        parse_nanos(duration).map(|_| duration)
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -217,8 +302,48 @@ mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.as_ref().unwrap().time, "74.13ns");
+            assert_eq!(res.part_2.unwrap().time, "74.13ms");
+            assert_eq!(res.part_1.unwrap().allocations, None);
+        }
+
+        #[test]
+        fn test_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 1.5 KiB peak]".into(),
+                    "Part 2: [1] (1ms @ 10 samples) [0 allocs, 0 B peak]".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let (part_1, part_2) = (res.part_1.unwrap(), res.part_2.unwrap());
+            assert_eq!(part_1.time, "74.13ns");
+            assert_eq!(part_1.allocations.unwrap(), "3 allocs, 1.5 KiB peak");
+            assert_eq!(part_2.time, "1ms");
+            assert_eq!(part_2.allocations.unwrap(), "0 allocs, 0 B peak");
+        }
+
+        #[test]
+        fn test_stddev_and_parse_time() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns ±1.5ns @ 100000 samples)".into(),
+                    "        memo: 3 hits, 1 miss".into(),
+                    "        parse: 12.5µs".into(),
+                    "Part 2: 10 (1.2ms @ 10 samples)".into(),
+                    "parse: 1.0ms".into(),
+                ],
+                day!(1),
+            );
+            let (part_1, part_2) = (res.part_1.unwrap(), res.part_2.unwrap());
+            assert_eq!(part_1.time, "74.13ns");
+            assert_eq!(part_1.stddev.unwrap(), "1.5ns");
+            assert_eq!(part_1.samples, Some(100000));
+            assert_eq!(part_1.parse.unwrap(), "12.5µs");
+            assert_eq!(part_2.stddev, None);
+            assert_eq!(part_2.parse, None);
+            assert_approx_eq!(res.total_nanos, 1_200_074.13_f64);
         }
 
         #[test]
@@ -232,8 +357,8 @@ mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().time, "2s");
+            assert_eq!(res.part_2.unwrap().time, "100ms");
         }
 
         #[test]
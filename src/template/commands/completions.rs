@@ -0,0 +1,13 @@
+use std::io;
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+use crate::template::cli::App;
+
+/// Prints the completion script of the `advent_of_code` binary for `shell`.
+pub fn handle(shell: Shell) {
+    let mut app = App::command();
+    let name = app.get_name().to_string();
+    clap_complete::generate(shell, &mut app, name, &mut io::stdout());
+}
@@ -0,0 +1,11 @@
+use std::env;
+
+use crate::template::config;
+
+/// Prints the effective configuration, with the source of every value.
+pub fn handle() {
+    let config = config::get();
+    let path = env::var(config::PATH_VAR).unwrap_or_else(|_| config::FILE_NAME.into());
+    println!("# effective configuration, read from {path} and $AOC_* overrides");
+    print!("{}", config.show());
+}
@@ -0,0 +1,109 @@
+#[cfg(unix)]
+use std::io::{self, IsTerminal};
+use std::process;
+
+#[cfg(unix)]
+use crate::template::cli::SolutionFlags;
+#[cfg(unix)]
+use crate::template::commands::{read, solve, test, verify};
+#[cfg(unix)]
+use crate::template::terminal::{Key, Screen};
+#[cfg(unix)]
+use crate::template::{calendar, config, dashboard};
+#[cfg(unix)]
+use crate::{day, Day};
+
+use crate::template::cli;
+
+/// Shows the calendar of the configured year and runs actions on the selected day until the
+/// dashboard is closed.
+#[cfg(unix)]
+pub fn handle() {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!("The dashboard needs an interactive terminal.");
+        process::exit(cli::EXIT_FAILURE);
+    }
+
+    let now = calendar::now();
+    let year = config::get()
+        .year
+        .unwrap_or_else(|| calendar::event_year(now));
+    let selected = calendar::latest_unlock(year, now).unwrap_or(day!(1));
+
+    if let Err(e) = run(year, selected) {
+        eprintln!("Dashboard failed: {e}");
+        process::exit(cli::EXIT_FAILURE);
+    }
+}
+
+/// The dashboard switches the terminal to raw mode with `stty`, which only exists on Unix.
+#[cfg(not(unix))]
+pub fn handle() {
+    eprintln!("The dashboard is only available on Unix-like systems, e.g. Linux and macOS.");
+    process::exit(cli::EXIT_FAILURE);
+}
+
+#[cfg(unix)]
+fn run(year: u16, mut selected: Day) -> io::Result<()> {
+    let mut screen = Screen::enter()?;
+    let mut message = String::new();
+
+    loop {
+        // checks record their outcome, which is read back with the rest of the state.
+        let days = dashboard::collect(year, calendar::now());
+        screen.draw(&dashboard::render(year, &days, selected, &message))?;
+        message.clear();
+
+        let key = screen.read_key()?;
+        let scaffolded = days
+            .iter()
+            .any(|status| status.day == selected && status.scaffolded);
+        if matches!(key, Key::Char('r' | 't' | 'v' | '1' | '2')) && !scaffolded {
+            message = format!("Day {selected} is not scaffolded yet.");
+            continue;
+        }
+
+        match key {
+            Key::Char('q') | Key::Escape | Key::Interrupt => return Ok(()),
+            Key::Left | Key::Char('h') => selected = step(selected, -1),
+            Key::Right | Key::Char('l') => selected = step(selected, 1),
+            Key::Up | Key::Char('k') => selected = step(selected, -5),
+            Key::Down | Key::Char('j') => selected = step(selected, 5),
+            Key::Char('r') | Key::Enter => {
+                screen.suspend(|| solve::solve(selected.into(), &solve::Options::default()))?;
+            }
+            Key::Char('t') => {
+                screen.suspend(|| test::test(selected.into()))?;
+            }
+            Key::Char('v') => {
+                screen.suspend(|| verify::verify(selected.into()))?;
+            }
+            Key::Char('p') => {
+                // the dashboard works offline, puzzles are read from the cache.
+                screen.suspend(|| {
+                    if let Err(e) = read::read(selected, true) {
+                        eprintln!("{e}");
+                    }
+                })?;
+            }
+            Key::Char(part @ ('1' | '2')) => {
+                let options = solve::Options {
+                    solution: SolutionFlags {
+                        submit_part: part.to_digit(10).map(|part| part as u8),
+                        ..SolutionFlags::default()
+                    },
+                    ..solve::Options::default()
+                };
+                screen.suspend(|| solve::solve(selected.into(), &options))?;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Moves the selection by `offset` days, staying on the calendar.
+#[cfg(unix)]
+fn step(day: Day, offset: i8) -> Day {
+    let target = day.into_inner() as i8 + offset;
+    u8::try_from(target).ok().and_then(Day::new).unwrap_or(day)
+}
@@ -1,15 +1,38 @@
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::aoc_cli::{self, AocCommandError};
+use crate::template::{cli, select_days, stats};
+use crate::{Day, DaySelection};
 use std::process;
 
-pub fn handle(day: Day) {
+pub fn handle(selection: DaySelection) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+        process::exit(cli::EXIT_FAILURE);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    let days = select_days(selection);
+    if days.is_empty() {
+        println!("No days match `{selection}`.");
+    }
+
+    // keep downloading the other days if one fails, e.g. because it has not unlocked yet.
+    let mut failed = false;
+    for day in days {
+        if let Err(e) = download(day) {
+            eprintln!("failed to call aoc-cli for day {day}: {e}");
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(cli::EXIT_FAILURE);
+    }
+}
+
+/// Downloads the input and puzzle of a day, and records the download for `stats`.
+pub fn download(day: Day) -> Result<(), AocCommandError> {
+    aoc_cli::download(day)?;
+
+    if let Err(e) = stats::record(stats::Event::now(day, stats::Kind::Download)) {
+        eprintln!("failed to record download: {e}");
+    }
+    Ok(())
 }
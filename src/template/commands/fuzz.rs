@@ -0,0 +1,120 @@
+use std::process::{self, Stdio};
+
+use crate::template::{ansi, cargo, cli, fuzz, is_scaffolded, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, DaySet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    PanicFree,
+    Panics,
+    Hangs,
+    Failed,
+}
+
+/// Fuzzes every scaffolded day and reports which ones are panic-free on arbitrary input.
+pub fn handle(is_release: bool, iterations: Option<usize>) {
+    let mut results: Vec<(Day, Status)> = vec![];
+    let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+
+    let scaffolded: DaySet = DaySet::all()
+        .iter()
+        .filter(|&day| is_scaffolded(day))
+        .collect();
+    let build_options = cargo::BuildOptions {
+        release: is_release,
+        features: None,
+        tests: false,
+    };
+    let artifacts = cargo::build(scaffolded, &build_options).unwrap_or_else(|e| {
+        eprintln!("Failed to build the solutions: {e}");
+        process::exit(cli::EXIT_FAILURE);
+    });
+    let iterations = iterations.unwrap_or(fuzz::DEFAULT_ITERATIONS).to_string();
+
+    for day in scaffolded {
+        println!("{bold}Day {day}{reset}");
+        println!("------");
+
+        // days that failed to build have no executable, and count as failed.
+        let code = artifacts.get(day).and_then(|executable| {
+            cargo::command(executable, &["--fuzz".to_string(), iterations.clone()])
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .status()
+                .ok()?
+                .code()
+        });
+
+        let status = match code {
+            Some(0) => Status::PanicFree,
+            Some(fuzz::EXIT_PANIC) => Status::Panics,
+            Some(fuzz::EXIT_HANG) => Status::Hangs,
+            _ => Status::Failed,
+        };
+        results.push((day, status));
+        println!();
+    }
+
+    println!("{bold}Summary{reset}");
+    println!("------");
+    for (day, status) in &results {
+        let status = match status {
+            Status::PanicFree => "✔ panic-free",
+            Status::Panics => "✖ panics",
+            Status::Hangs => "✖ hangs",
+            Status::Failed => "✖ could not be fuzzed",
+        };
+        println!("Day {day}: {status}");
+    }
+
+    let panic_free = results
+        .iter()
+        .filter(|(_, s)| matches!(s, Status::PanicFree))
+        .count();
+    println!("{panic_free}/{} days are panic-free.", results.len());
+
+    let exit_code = exit_code(results.iter().map(|(_, status)| *status));
+    if exit_code != 0 {
+        process::exit(exit_code);
+    }
+}
+
+/// Exit code of a fuzzing run: [`fuzz::EXIT_PANIC`] if a day panics, else [`fuzz::EXIT_HANG`] if
+/// a day hangs, else [`cli::EXIT_FAILURE`] if a day could not be fuzzed.
+fn exit_code(statuses: impl Iterator<Item = Status>) -> i32 {
+    let statuses: Vec<_> = statuses.collect();
+    [
+        (Status::Panics, fuzz::EXIT_PANIC),
+        (Status::Hangs, fuzz::EXIT_HANG),
+        (Status::Failed, cli::EXIT_FAILURE),
+    ]
+    .into_iter()
+    .find(|(status, _)| statuses.contains(status))
+    .map_or(0, |(_, code)| code)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{exit_code, Status};
+    use crate::template::{cli, fuzz};
+
+    #[test]
+    fn maps_statuses_to_exit_codes() {
+        assert_eq!(exit_code([].into_iter()), 0);
+        assert_eq!(exit_code([Status::PanicFree].into_iter()), 0);
+        assert_eq!(
+            exit_code([Status::PanicFree, Status::Failed].into_iter()),
+            cli::EXIT_FAILURE
+        );
+        assert_eq!(
+            exit_code([Status::Failed, Status::Hangs].into_iter()),
+            fuzz::EXIT_HANG
+        );
+        assert_eq!(
+            exit_code([Status::Hangs, Status::Panics, Status::Failed].into_iter()),
+            fuzz::EXIT_PANIC
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::PathBuf;
+use std::{fs, process};
+
+use clap::Args;
+
+use crate::template::leaderboard::{self, Source};
+use crate::template::{aoc_cli, calendar, cli, config};
+use crate::Day;
+
+/// Flags of the `leaderboard` command.
+#[derive(Debug, Default, Args)]
+pub struct Options {
+    /// Shows the completion times of a day instead of the overview.
+    #[arg(long)]
+    pub day: Option<Day>,
+    /// Only reads the cached leaderboard.
+    #[arg(long)]
+    pub offline: bool,
+    /// Writes the leaderboard as JSON to this file.
+    #[arg(long, value_name = "FILE")]
+    pub export: Option<PathBuf>,
+}
+
+pub fn handle(id: Option<u64>, options: Options) {
+    // your own private leaderboard has the id of your user.
+    let Some(id) = id.or_else(aoc_cli::get_user_id) else {
+        eprintln!("No leaderboard id passed and AOC_USER_ID is not set.");
+        process::exit(cli::EXIT_FAILURE);
+    };
+    let now = calendar::now();
+    let year = config::get()
+        .year
+        .unwrap_or_else(|| calendar::event_year(now));
+
+    let (leaderboard, source) = match leaderboard::load(year, id, options.offline) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Failed to load leaderboard: {e}");
+            process::exit(cli::EXIT_FAILURE);
+        }
+    };
+
+    match source {
+        Source::Fetched => println!("Fetched private leaderboard {id} of {year}."),
+        Source::Cache(age) => println!(
+            "Loaded private leaderboard {id} of {year} from cache ({} old).",
+            calendar::format_duration(age.as_secs() as i64)
+        ),
+    }
+    println!();
+
+    match options.day {
+        Some(day) => print!("{}", leaderboard.render_day(day)),
+        None => print!("{}", leaderboard.render(now)),
+    }
+
+    if let Some(path) = options.export {
+        match fs::write(&path, format!("{:#}\n", leaderboard.to_json())) {
+            Ok(()) => println!("Wrote leaderboard to {}.", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write leaderboard: {e}");
+                process::exit(cli::EXIT_FAILURE);
+            }
+        }
+    }
+}
@@ -1,5 +1,15 @@
 pub mod all;
+pub mod completions;
+pub mod config;
+pub mod dashboard;
 pub mod download;
+pub mod fuzz;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod stats;
+pub mod test;
+pub mod verify;
+pub mod wait;
@@ -1,16 +1,92 @@
-use std::process;
+use std::path::PathBuf;
+use std::{fs, process};
 
-use crate::template::aoc_cli;
+use crate::template::html::{self, Format};
+use crate::template::{aoc_client, calendar, cli, config};
 use crate::Day;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+fn get_puzzle_path(day: Day, extension: &str) -> PathBuf {
+    config::get()
+        .paths
+        .folder("puzzles")
+        .join(format!("{day}.{extension}"))
+}
+
+/// Renders all descriptions of a day page, part one and part two if it is unlocked.
+fn render(page: &str, format: Format) -> String {
+    html::articles(page)
+        .iter()
+        .map(|article| html::render(article, format))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Fetches the day page and caches it as HTML and as Markdown in `data/puzzles/`.
+fn fetch(year: u16, day: Day) -> Result<String, String> {
+    let page =
+        aoc_client::get(&format!("/{year}/day/{}", day.into_inner())).map_err(|e| e.to_string())?;
+    if html::articles(&page).is_empty() {
+        return Err("the page contains no puzzle description.".into());
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let html_path = get_puzzle_path(day, "html");
+    if let Some(folder) = html_path.parent() {
+        fs::create_dir_all(folder).map_err(|e| e.to_string())?;
+    }
+    fs::write(&html_path, &page).map_err(|e| e.to_string())?;
+    fs::write(get_puzzle_path(day, "md"), render(&page, Format::Markdown))
+        .map_err(|e| e.to_string())?;
+    Ok(page)
+}
+
+/// Shows the puzzle description of a day from `data/puzzles/`. The page is fetched again until
+/// it contains part two, unless `offline` is set.
+pub fn handle(day: Day, offline: bool) {
+    if let Err(e) = read(day, offline) {
+        eprintln!("{e}");
+        process::exit(cli::EXIT_FAILURE);
+    }
+}
+
+/// Prints the puzzle description of a day, see [`handle`].
+pub fn read(day: Day, offline: bool) -> Result<(), String> {
+    let now = calendar::now();
+    let year = config::get()
+        .year
+        .unwrap_or_else(|| calendar::event_year(now));
+    let html_path = get_puzzle_path(day, "html");
+    let cached = fs::read_to_string(&html_path).ok();
+    let complete = cached
+        .as_deref()
+        .is_some_and(|page| html::articles(page).len() >= 2);
+
+    let page = if offline || complete {
+        cached
+    } else if now < calendar::unlock_time(year, day) {
+        return Err(format!("Day {day} of {year} is not unlocked yet."));
+    } else {
+        match fetch(year, day) {
+            Ok(page) => Some(page),
+            Err(e) if cached.is_some() => {
+                eprintln!("Could not fetch the puzzle ({e}), showing the cached description.");
+                cached
+            }
+            Err(e) => return Err(format!("Could not fetch the puzzle: {e}")),
+        }
+    };
+
+    let Some(page) = page else {
+        return Err(format!(
+            "No cached puzzle at \"{}\", run `cargo read {day}` while online first.",
+            html_path.display()
+        ));
+    };
+
+    let format = if config::get().output.color.enabled() {
+        Format::Terminal
+    } else {
+        Format::Markdown
     };
+    print!("{}", render(&page, format));
+    Ok(())
 }
@@ -1,18 +1,22 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::Day;
+use crate::template::{cli, config, is_scaffolded, select_days, stats};
+use crate::{Day, DaySelection};
+
+const FUZZ_MANIFEST: &str = "fuzz/Cargo.toml";
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<u64> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<u64> {
     None
 }
 
@@ -22,36 +26,130 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_input("examples", DAY, INPUT));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_input("examples", DAY, INPUT));
         assert_eq!(result, None);
     }
 }
 "#;
 
+const FUZZ_TARGET_TEMPLATE: &str = r#"#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[allow(dead_code)]
+mod solution {
+    include!("BIN_PATH");
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = solution::part_one(input);
+        let _ = solution::part_two(input);
+    }
+});
+"#;
+
+const FUZZ_BIN_TEMPLATE: &str = r#"
+[[bin]]
+name = "DAY_PADDED"
+path = "fuzz_targets/DAY_PADDED.rs"
+test = false
+doc = false
+bench = false
+"#;
+
+/// Creates the folder of `path`, which may be configured to one that does not exist yet.
+fn create_parent(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(folder) => fs::create_dir_all(folder),
+        None => Ok(()),
+    }
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent(path)?;
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    create_parent(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+fn create_fuzz_target(day: Day, module_path: &str) -> Result<String, std::io::Error> {
+    let target_path = format!("fuzz/fuzz_targets/{day}.rs");
+    // the target is included relative to `fuzz/fuzz_targets/`.
+    let include_path = if Path::new(module_path).is_relative() {
+        format!("../../{module_path}")
+    } else {
+        module_path.to_string()
+    };
+    safe_create_file(&target_path)?.write_all(
+        FUZZ_TARGET_TEMPLATE
+            .replace("BIN_PATH", &include_path)
+            .as_bytes(),
+    )?;
+    OpenOptions::new()
+        .append(true)
+        .open(FUZZ_MANIFEST)?
+        .write_all(
+            FUZZ_BIN_TEMPLATE
+                .replace("DAY_PADDED", &day.to_string())
+                .as_bytes(),
+        )?;
+    Ok(target_path)
+}
+
+pub fn handle(selection: DaySelection) {
+    let days = select_days(selection);
+    if days.len() == 1 {
+        // a single day reports an existing solution as an error.
+        days.iter().for_each(scaffold);
+        return;
+    }
+    if days.is_empty() {
+        println!("No days match `{selection}`.");
+    }
+    for day in days {
+        if is_scaffolded(day) {
+            println!("Day {day} is scaffolded already, skipping it.");
+        } else {
+            scaffold(day);
+        }
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the solution, input and example files of a day, exits if one cannot be created.
+pub fn scaffold(day: Day) {
+    let paths = &config::get().paths;
+    let file_name = format!("{day}.txt");
+    let input_path = paths
+        .folder("inputs")
+        .join(&file_name)
+        .display()
+        .to_string();
+    let example_path = paths
+        .folder("examples")
+        .join(&file_name)
+        .display()
+        .to_string();
+    let module_path = paths.bin(day).display().to_string();
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+            process::exit(cli::EXIT_FAILURE);
         }
     };
 
@@ -65,7 +163,7 @@ pub fn handle(day: Day) {
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+            process::exit(cli::EXIT_FAILURE);
         }
     }
 
@@ -75,7 +173,7 @@ pub fn handle(day: Day) {
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+            process::exit(cli::EXIT_FAILURE);
         }
     }
 
@@ -85,10 +183,22 @@ pub fn handle(day: Day) {
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+            process::exit(cli::EXIT_FAILURE);
         }
     }
 
+    // the cargo-fuzz crate is optional, only extend it when it is set up.
+    if Path::new(FUZZ_MANIFEST).exists() {
+        match create_fuzz_target(day, &module_path) {
+            Ok(path) => println!("Created fuzz target \"{path}\""),
+            Err(e) => eprintln!("Failed to create fuzz target: {e}"),
+        }
+    }
+
+    if let Err(e) = stats::record(stats::Event::now(day, stats::Kind::Scaffold)) {
+        eprintln!("Failed to record scaffolding: {e}");
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
 }
@@ -1,31 +1,96 @@
-use std::process::{Command, Stdio};
+use std::path::Path;
+use std::process;
 
-use crate::Day;
+use clap::Args;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+use crate::template::cli::SolutionFlags;
+use crate::template::{ansi, cargo, cli, is_scaffolded, select_days, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, DaySelection, DaySet};
 
-    if release {
-        cmd_args.push("--release".to_string());
-    }
-
-    cmd_args.push("--".to_string());
+/// Flags of the `solve` command.
+#[derive(Debug, Default, Args)]
+pub struct Options {
+    /// Builds the solutions in release mode.
+    #[arg(long)]
+    pub release: bool,
+    /// Cargo features of the build, e.g. `mmap` or `count_allocations`.
+    #[arg(long)]
+    pub features: Option<String>,
+    /// Flags mirrored to the solution binary.
+    #[command(flatten)]
+    pub solution: SolutionFlags,
+}
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+/// Builds and runs the solutions of the selected days. Exits with the exit code of a failing
+/// solution, or with a failure if a solution does not build.
+pub fn handle(selection: DaySelection, options: Options) {
+    let exit_code = solve(selection, &options);
+    if exit_code != 0 {
+        process::exit(exit_code);
     }
+}
 
-    if time {
-        cmd_args.push("--time".to_string());
+/// Builds and runs the solutions of the selected days, returns the highest exit code of the
+/// solutions, or [`cli::EXIT_FAILURE`] if one does not build.
+pub fn solve(selection: DaySelection, options: &Options) -> i32 {
+    let days = select_days(selection);
+    if days.is_empty() {
+        println!("No days match `{selection}`.");
+        return 0;
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    // a single day is built even if it is not scaffolded, to show cargo's error.
+    let is_single = days.len() == 1;
+    let built: DaySet = days
+        .iter()
+        .filter(|&day| is_single || is_scaffolded(day))
+        .collect();
+    let build_options = cargo::BuildOptions {
+        release: options.release,
+        features: options.features.clone(),
+        tests: false,
+    };
+    let artifacts = match cargo::build(built, &build_options) {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            eprintln!("Failed to build the solutions: {e}");
+            return cli::EXIT_FAILURE;
+        }
+    };
+
+    let args = options.solution.to_args();
+    let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    let mut exit_code = 0;
 
-    cmd.wait().unwrap();
+    for (i, day) in days.iter().enumerate() {
+        if !is_single {
+            if i > 0 {
+                println!();
+            }
+            println!("{bold}Day {day}{reset}");
+            println!("------");
+        }
+
+        if !built.contains(day) {
+            println!("Not scaffolded.");
+            continue;
+        }
+        exit_code = exit_code.max(run(day, artifacts.get(day), &args));
+    }
+    exit_code
+}
+
+/// Runs the solution of a day and returns its exit code.
+fn run(day: Day, executable: Option<&Path>, args: &[String]) -> i32 {
+    let Some(executable) = executable else {
+        eprintln!("Day {day} failed to build.");
+        return cli::EXIT_FAILURE;
+    };
+    match cargo::command(executable, args).status() {
+        Ok(status) => cargo::exit_code(status),
+        Err(e) => {
+            eprintln!("Failed to run day {day}: {e}");
+            cli::EXIT_FAILURE
+        }
+    }
 }
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use std::{fs, process};
+
+use crate::template::readme_benchmarks::Error;
+use crate::template::{aoc_cli, cli, config, readme_stars};
+
+/// Updates the star table of the readme from the recorded answers, and from a downloaded private
+/// leaderboard if one is passed.
+pub fn handle(leaderboard: Option<PathBuf>, user_id: Option<u64>) {
+    let mut stars = readme_stars::from_answers().unwrap_or_else(|_| {
+        eprintln!("Failed to read recorded answers.");
+        process::exit(cli::EXIT_FAILURE);
+    });
+
+    if let Some(path) = leaderboard {
+        let leaderboard = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string()));
+        let leaderboard = match leaderboard {
+            Ok(leaderboard) => leaderboard,
+            Err(e) => {
+                eprintln!("Failed to read leaderboard \"{}\": {e}", path.display());
+                process::exit(cli::EXIT_FAILURE);
+            }
+        };
+        match readme_stars::from_leaderboard(&leaderboard, user_id.or_else(aoc_cli::get_user_id)) {
+            Ok(member_stars) => stars = readme_stars::merge(&stars, &member_stars),
+            Err(Error::Parser(e)) => {
+                eprintln!("Failed to read stars from leaderboard: {e}");
+                process::exit(cli::EXIT_FAILURE);
+            }
+            Err(Error::IO(e)) => {
+                eprintln!("Failed to read stars from leaderboard: {e}");
+                process::exit(cli::EXIT_FAILURE);
+            }
+        }
+    }
+
+    match readme_stars::update(&stars, config::get().year) {
+        Ok(()) => println!("Successfully updated README with stars."),
+        Err(_) => {
+            eprintln!("Failed to update readme with stars.");
+            process::exit(cli::EXIT_FAILURE);
+        }
+    }
+}
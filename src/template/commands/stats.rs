@@ -0,0 +1,20 @@
+use std::process;
+
+use crate::template::{cli, stats};
+
+pub fn handle(year: Option<u16>) {
+    let events = match stats::read() {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", stats::get_path().display());
+            process::exit(cli::EXIT_FAILURE);
+        }
+    };
+
+    if events.is_empty() {
+        println!("No stats recorded yet. Scaffolding, downloading and submitting record them.");
+        return;
+    }
+
+    print!("{}", stats::render(&stats::summarize(&events), year));
+}
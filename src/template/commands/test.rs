@@ -0,0 +1,183 @@
+use std::path::Path;
+use std::process::{self, Stdio};
+
+use crate::template::checks::{self, Check};
+use crate::template::libtest::{self, Counts, Report};
+use crate::template::{
+    ansi, answers, cargo, cli, is_scaffolded, select_days, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, DaySelection, DaySet};
+
+/// Outcome of testing a selection of days.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Tests of all days.
+    pub counts: Counts,
+    /// Days whose tests failed to build or to run.
+    pub failed: usize,
+}
+
+impl Summary {
+    pub fn is_success(&self) -> bool {
+        self.counts.failed == 0 && self.failed == 0
+    }
+}
+
+/// Runs the tests of the selected days and prints their outcome per part. Exits with a failure
+/// if a test fails or the tests of a day do not build.
+pub fn handle(selection: DaySelection) {
+    if !test(selection).is_success() {
+        process::exit(cli::EXIT_FAILURE);
+    }
+}
+
+/// Builds the test harnesses of the selected days at once, runs them and prints the outcome of
+/// every day, followed by a summary of all days. The outcome of every day is recorded for the
+/// dashboard.
+pub fn test(selection: DaySelection) -> Summary {
+    let mut summary = Summary::default();
+    let days = select_days(selection);
+    if days.is_empty() {
+        println!("No days match `{selection}`.");
+        return summary;
+    }
+
+    let scaffolded: DaySet = days.iter().filter(|&day| is_scaffolded(day)).collect();
+    let build_options = cargo::BuildOptions {
+        tests: true,
+        ..cargo::BuildOptions::default()
+    };
+    let artifacts = match cargo::build(scaffolded, &build_options) {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            eprintln!("Failed to build the tests: {e}");
+            summary.failed = scaffolded.len();
+            for day in scaffolded {
+                record(day, Check::Failed);
+            }
+            return summary;
+        }
+    };
+
+    let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    let mut failures = vec![];
+    for day in scaffolded {
+        let status = solution_status(day);
+        let report = match artifacts.get(day) {
+            None => {
+                println!("{bold}Day {day}{reset}: ✖ failed to build · {status}");
+                summary.failed += 1;
+                record(day, Check::Failed);
+                continue;
+            }
+            Some(executable) => run_tests(executable),
+        };
+
+        let mut parts: Vec<String> = report
+            .parts
+            .iter()
+            .zip(1..)
+            .map(|(counts, part)| describe_part(part, *counts))
+            .collect();
+        let other = report.other;
+        if other.passed + other.failed > 0 {
+            parts.push(match other.failed {
+                0 => format!("{} more ✔", other.passed),
+                failed => format!("{} more, {failed} ✖", other.passed + failed),
+            });
+        }
+        let ignored = report.total().ignored;
+        if ignored > 0 {
+            parts.push(format!("{ignored} ignored"));
+        }
+        if !report.finished {
+            parts.push("✖ crashed".to_string());
+            summary.failed += 1;
+        }
+        println!("{bold}Day {day}{reset}: {} · {status}", parts.join(", "));
+
+        for failure in &report.failures {
+            println!("    {} failed:", failure.name);
+            for line in failure.stdout.trim_matches('\n').lines() {
+                println!("      {line}");
+            }
+            failures.push(format!("day {day} {}", failure.name));
+        }
+        let total = report.total();
+        record(
+            day,
+            if total.failed > 0 || !report.finished {
+                Check::Failed
+            } else if total.passed > 0 {
+                Check::Passed
+            } else {
+                Check::Unknown
+            },
+        );
+        summary.counts.merge(total);
+    }
+
+    println!("---");
+    println!(
+        "{} tests passed, {} failed, {} ignored in {} days.",
+        summary.counts.passed,
+        summary.counts.failed,
+        summary.counts.ignored,
+        scaffolded.len()
+    );
+    for failure in failures {
+        println!("Failed: {failure}");
+    }
+    if summary.failed > 0 {
+        println!(
+            "{} days failed to build or run their tests.",
+            summary.failed
+        );
+    }
+    summary
+}
+
+/// Records the outcome of the tests of a day.
+fn record(day: Day, examples: Check) {
+    if let Err(e) = checks::record(day, |checks| checks.examples = examples) {
+        eprintln!("Failed to record the tests of day {day}: {e}");
+    }
+}
+
+/// Runs a built test harness and reads its outcome. A harness that cannot be started reports
+/// no result, so it counts as crashed.
+fn run_tests(executable: &Path) -> Report {
+    let output = cargo::command(executable, &libtest::ARGS.map(String::from))
+        .stderr(Stdio::inherit())
+        .output();
+    match output {
+        Ok(output) => Report::parse(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            eprintln!("Failed to run the tests: {e}");
+            Report::default()
+        }
+    }
+}
+
+fn describe_part(part: u8, counts: Counts) -> String {
+    if counts.failed > 0 {
+        format!("part {part} ✖")
+    } else if counts.passed > 0 {
+        format!("part {part} ✔")
+    } else if counts.ignored > 0 {
+        format!("part {part} ignored")
+    } else {
+        format!("part {part} untested")
+    }
+}
+
+/// Whether the puzzle of a day is solved, by its recorded answers.
+fn solution_status(day: Day) -> String {
+    let answers = answers::read(day).unwrap_or_default();
+    match (answers.part_1.is_some(), answers.part_2.is_some()) {
+        (true, true) => "solved ★★".to_string(),
+        (true, false) => "part 1 solved ★".to_string(),
+        (false, true) => "part 2 solved ★".to_string(),
+        (false, false) => "unsolved".to_string(),
+    }
+}
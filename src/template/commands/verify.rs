@@ -0,0 +1,201 @@
+use std::path::Path;
+use std::process::{self, Stdio};
+
+use crate::template::answers::{self, Verdict};
+use crate::template::checks::{self, Check};
+use crate::template::{
+    ansi, cargo, cli, config, is_scaffolded, select_days, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, DaySelection, DaySet};
+
+/// Outcome of verifying a selection of days.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Parts whose result matches the recorded answer.
+    pub correct: usize,
+    /// Parts whose result differs from the recorded answer.
+    pub wrong: usize,
+    /// Parts that were checked, including the ones without a result or a recorded answer.
+    pub total: usize,
+    /// Days whose solution failed to build or to run.
+    pub failed: usize,
+}
+
+impl Summary {
+    pub fn is_success(&self) -> bool {
+        self.wrong == 0 && self.failed == 0
+    }
+}
+
+/// Runs the solutions of the selected days in release mode and compares their results with the
+/// answers recorded in `data/answers/`. Exits with a failure if a result is wrong or a solution
+/// fails.
+pub fn handle(selection: DaySelection) {
+    if !verify(selection).is_success() {
+        process::exit(cli::EXIT_FAILURE);
+    }
+}
+
+/// Verifies the selected days and prints the verdict of every part. The outcome of every day is
+/// recorded for the dashboard.
+pub fn verify(selection: DaySelection) -> Summary {
+    let mut summary = Summary::default();
+    let days = select_days(selection);
+    if days.is_empty() {
+        println!("No days match `{selection}`.");
+        return summary;
+    }
+
+    let scaffolded: DaySet = days.iter().filter(|&day| is_scaffolded(day)).collect();
+    let build_options = cargo::BuildOptions {
+        release: true,
+        features: None,
+        tests: false,
+    };
+    let artifacts = match cargo::build(scaffolded, &build_options) {
+        Ok(artifacts) => artifacts,
+        Err(e) => {
+            eprintln!("Failed to build the solutions: {e}");
+            summary.failed = scaffolded.len();
+            for day in scaffolded {
+                record(day, Check::Failed);
+            }
+            return summary;
+        }
+    };
+
+    let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    for day in scaffolded {
+        let answers = match answers::read(day) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("Failed to read the answers of day {day}: {e}");
+                summary.failed += 1;
+                record(day, Check::Failed);
+                continue;
+            }
+        };
+        let Some(results) = artifacts.get(day).and_then(run_solution) else {
+            println!("{bold}Day {day}{reset}: ✖ failed to run");
+            summary.failed += 1;
+            record(day, Check::Failed);
+            continue;
+        };
+
+        let (correct, wrong) = (summary.correct, summary.wrong);
+        let parts: Vec<String> = [1, 2]
+            .into_iter()
+            .map(|part| {
+                summary.total += 1;
+                let result = results
+                    .iter()
+                    .find(|(p, _)| *p == part)
+                    .and_then(|(_, result)| result.as_deref());
+                match (
+                    result,
+                    answers::verify(&answers, part, result.unwrap_or("")),
+                ) {
+                    (None, _) => format!("part {part} not solved"),
+                    (Some(_), Verdict::Unknown) => format!("part {part} has no recorded answer"),
+                    (Some(_), Verdict::Correct) => {
+                        summary.correct += 1;
+                        format!("part {part} ✔")
+                    }
+                    (Some(result), Verdict::Wrong(answer)) => {
+                        summary.wrong += 1;
+                        format!("part {part} ✖ {result}, expected {answer}")
+                    }
+                }
+            })
+            .collect();
+        println!("{bold}Day {day}{reset}: {}", parts.join(", "));
+        record(
+            day,
+            if summary.wrong > wrong {
+                Check::Failed
+            } else if summary.correct > correct {
+                Check::Passed
+            } else {
+                Check::Unknown
+            },
+        );
+    }
+
+    println!("---");
+    println!(
+        "{}/{} parts match their recorded answer, {} wrong.",
+        summary.correct, summary.total, summary.wrong
+    );
+    if summary.failed > 0 {
+        println!("{} solutions failed to run.", summary.failed);
+    }
+    summary
+}
+
+/// Records the outcome of verifying a day.
+fn record(day: Day, answers: Check) {
+    if let Err(e) = checks::record(day, |checks| checks.answers = answers) {
+        eprintln!("Failed to record the verification of day {day}: {e}");
+    }
+}
+
+/// Runs a built solution and returns the result of each part it printed, [`None`] if the
+/// solution could not be run or failed.
+fn run_solution(executable: &Path) -> Option<Vec<(u8, Option<String>)>> {
+    let output = cargo::command(executable, &[])
+        // results are parsed, they must not contain colour codes.
+        .env(config::env_var("output.color"), "never")
+        .stderr(Stdio::inherit())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(parse_result)
+            .collect(),
+    )
+}
+
+/// Parses a result line of the runner, e.g. `Part 1: 42 (1.2ms)`. The result is [`None`] for
+/// unsolved parts and for results spanning several lines, which cannot be recorded.
+fn parse_result(line: &str) -> Option<(u8, Option<String>)> {
+    // the runner prints the result before it is timed, then overwrites the line.
+    let line = line.rsplit('\r').next()?;
+    let (part, rest) = line.strip_prefix("Part ")?.split_once(": ")?;
+    let part = part.parse().ok()?;
+    let result = match rest.rsplit_once(" (") {
+        _ if rest.starts_with('✖') || rest.starts_with('▼') => None,
+        Some((result, _)) => Some(result.to_string()),
+        None => Some(rest.trim_end().to_string()),
+    };
+    Some((part, result))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_result;
+
+    #[test]
+    fn parses_results() {
+        assert_eq!(
+            parse_result("Part 1: 42 (1.2ms)"),
+            Some((1, Some("42".into())))
+        );
+        assert_eq!(
+            parse_result("Part 2: a (b) (5.9µs ±0.3µs @ 7558 samples) [1 allocs, 8 B peak]"),
+            Some((2, Some("a (b)".into())))
+        );
+        assert_eq!(
+            parse_result("Part 1: 42 > benching\rPart 1: 42 (1.2ms @ 10 samples)"),
+            Some((1, Some("42".into())))
+        );
+        assert_eq!(parse_result("Part 1: ✖             "), Some((1, None)));
+        assert_eq!(parse_result("Part 2: ▼ (1.0ms)"), Some((2, None)));
+        assert_eq!(parse_result("Profile"), None);
+    }
+}
@@ -0,0 +1,104 @@
+use std::io::{stdout, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use crate::template::commands::{download, scaffold};
+use crate::template::readme_benchmarks::get_path_for_bin;
+use crate::template::{ansi, aoc_cli, calendar, cli, config, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+/// Downloads are attempted this many times before giving up.
+const MAX_ATTEMPTS: u32 = 8;
+/// Waited after the unlock before the first download, in case the clocks are slightly off.
+const GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Delay before retrying a failed download, doubling from 5 seconds up to a minute.
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs((5 << attempt.min(4)).min(60))
+}
+
+/// Waits for a puzzle to unlock, then scaffolds and downloads it.
+/// Without `day`, the next puzzle of the configured year is awaited.
+pub fn handle(day: Option<Day>) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(cli::EXIT_FAILURE);
+    }
+
+    let now = calendar::now();
+    let year = config::get()
+        .year
+        .unwrap_or_else(|| calendar::event_year(now));
+    let Some(day) = day.or_else(|| calendar::next_unlock(year, now)) else {
+        eprintln!("All puzzles of {year} are unlocked, pass the day to download.");
+        process::exit(cli::EXIT_FAILURE);
+    };
+
+    let unlock = calendar::unlock_time(year, day);
+    println!(
+        "Day {day} of {year} unlocks at {}.",
+        calendar::format_timestamp(unlock)
+    );
+    countdown(day, unlock);
+    thread::sleep(GRACE_PERIOD);
+
+    // scaffolding refuses to overwrite an existing solution.
+    if Path::new(&get_path_for_bin(day)).exists() {
+        println!("Solution for day {day} exists already, skipping scaffold.");
+    } else {
+        scaffold::scaffold(day);
+    }
+
+    for attempt in 0..MAX_ATTEMPTS {
+        match download::download(day) {
+            Ok(()) => return,
+            Err(e) if attempt + 1 < MAX_ATTEMPTS => {
+                let delay = backoff(attempt);
+                eprintln!("Download failed ({e}), retrying in {delay:?}...");
+                thread::sleep(delay);
+            }
+            Err(e) => {
+                eprintln!("Download failed ({e}), giving up after {MAX_ATTEMPTS} attempts.");
+                process::exit(cli::EXIT_FAILURE);
+            }
+        }
+    }
+}
+
+/// Prints the remaining time every second until `unlock` has passed.
+fn countdown(day: Day, unlock: i64) {
+    let mut stdout = stdout();
+    loop {
+        let remaining = unlock - calendar::now();
+        if remaining <= 0 {
+            break;
+        }
+        print!(
+            "\r⏳ Day {day} unlocks in {}{}{} ",
+            ansi(ANSI_BOLD),
+            calendar::format_duration(remaining),
+            ansi(ANSI_RESET)
+        );
+        let _ = stdout.flush();
+        thread::sleep(Duration::from_secs(1));
+    }
+    println!("\r🎄 Day {day} is unlocked!                    ");
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::backoff;
+
+    #[test]
+    fn backs_off_exponentially() {
+        let delays: Vec<_> = (0..7).map(|a| backoff(a).as_secs()).collect();
+        assert_eq!(delays, [5, 10, 20, 40, 60, 60, 60]);
+        assert!(backoff(u32::MAX) <= Duration::from_secs(60));
+    }
+}
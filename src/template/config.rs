@@ -0,0 +1,544 @@
+/// Settings of the template tool, read from `aoc.toml` in the project root.
+///
+/// Every setting has a default and can be overridden with an environment variable named after
+/// its key, e.g. `AOC_BENCH_BUDGET_MS` for `bench.budget_ms`. The `--set <key>=<value>` flag
+/// sets that variable, so overrides are inherited by the solution binaries.
+use std::fmt::Display;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs, process};
+
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+use crate::template::cli;
+use crate::template::readme_benchmarks::{Column, Format, Sort, TableOptions};
+use crate::Day;
+
+pub const FILE_NAME: &str = "aoc.toml";
+
+/// Variable that points to another configuration file.
+pub const PATH_VAR: &str = "AOC_CONFIG";
+
+/// How the value of a key is written in an environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+    Integer,
+    String,
+    Bool,
+    List,
+}
+
+/// Known keys and the type of their value.
+const KEYS: &[(&str, Type)] = &[
+    ("year", Type::Integer),
+    ("paths.data", Type::String),
+    ("paths.inputs", Type::String),
+    ("paths.examples", Type::String),
+    ("paths.puzzles", Type::String),
+    ("paths.bin", Type::String),
+    ("paths.session", Type::String),
+    ("bench.budget_ms", Type::Integer),
+    ("bench.min_samples", Type::Integer),
+    ("bench.max_samples", Type::Integer),
+    ("bench.columns", Type::List),
+    ("bench.sort", Type::String),
+    ("bench.bars", Type::Bool),
+    ("bench.by_year", Type::Bool),
+    ("bench.formats", Type::List),
+    ("output.color", Type::String),
+];
+
+/// Name of the environment variable that overrides `key`, e.g. `AOC_OUTPUT_COLOR`.
+pub fn env_var(key: &str) -> String {
+    format!("AOC_{}", key.replace('.', "_").to_uppercase())
+}
+
+/// Where the value of a setting comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(String),
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "${var}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, io::Error),
+    /// The file is not valid TOML, or has unknown keys or values of the wrong type.
+    Parse(PathBuf, toml::de::Error),
+    InvalidValue {
+        key: String,
+        source: Source,
+        message: String,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(path, e) => write!(f, "could not read {}: {e}", path.display()),
+            Error::Parse(path, e) => write!(
+                f,
+                "could not parse {}: {}",
+                path.display(),
+                e.to_string().trim_end()
+            ),
+            Error::InvalidValue {
+                key,
+                source,
+                message,
+            } => write!(f, "invalid value of `{key}` in {source}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// When output is coloured with ANSI escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    /// When stdout is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl Color {
+    pub fn enabled(self) -> bool {
+        match self {
+            Color::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none(),
+            Color::Always => true,
+            Color::Never => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    /// Root of the generated files, e.g. answers, stats and benchmarks.
+    pub data: PathBuf,
+    /// Folders of the inputs, examples and puzzle descriptions, relative to `data`.
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    /// Source file of a day, `{day}` is replaced with the zero-padded day.
+    pub bin: String,
+    /// File containing the session cookie, passed on to aoc-cli.
+    pub session: Option<PathBuf>,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            data: "data".into(),
+            inputs: "inputs".into(),
+            examples: "examples".into(),
+            puzzles: "puzzles".into(),
+            bin: "src/bin/{day}.rs".into(),
+            session: None,
+        }
+    }
+}
+
+impl Paths {
+    /// The folder of inputs, examples or puzzles, or any other folder in `data`.
+    pub fn folder(&self, name: &str) -> PathBuf {
+        match name {
+            "inputs" => self.data.join(&self.inputs),
+            "examples" => self.data.join(&self.examples),
+            "puzzles" => self.data.join(&self.puzzles),
+            _ => self.data.join(name),
+        }
+    }
+
+    pub fn bin(&self, day: Day) -> PathBuf {
+        PathBuf::from(self.bin.replace("{day}", &day.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Bench {
+    /// Approximate time spent benchmarking each part.
+    #[serde(rename = "budget_ms", with = "millis")]
+    pub budget: Duration,
+    pub min_samples: u64,
+    pub max_samples: u64,
+    /// Defaults of the `all` flags, see [`Bench::table`].
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    pub bars: bool,
+    pub by_year: bool,
+    pub formats: Vec<Format>,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+            columns: vec![],
+            sort: Sort::Day,
+            bars: false,
+            by_year: false,
+            formats: vec![],
+        }
+    }
+}
+
+impl Bench {
+    /// Defaults of the table flags of `all`.
+    pub fn table(&self) -> TableOptions {
+        TableOptions {
+            columns: self.columns.clone(),
+            sort: self.sort,
+            bars: self.bars,
+            by_year: self.by_year,
+            formats: self.formats.clone(),
+        }
+    }
+}
+
+/// Reads and writes a [`Duration`] as a number of milliseconds.
+mod millis {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Output {
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub year: Option<u16>,
+    pub paths: Paths,
+    pub bench: Bench,
+    pub output: Output,
+    /// Where the value of every key comes from.
+    #[serde(skip)]
+    sources: Vec<(&'static str, Source)>,
+}
+
+/// Parses a value from the environment, lists may be comma-separated.
+fn parse_raw(kind: Type, raw: &str) -> Result<Value, String> {
+    let raw = raw.trim();
+    match kind {
+        Type::Integer => raw
+            .replace('_', "")
+            .parse()
+            .map(Value::Integer)
+            .map_err(|_| format!("expected an integer, got \"{raw}\"")),
+        Type::String => Ok(raw.into()),
+        Type::Bool => match raw {
+            "true" | "1" => Ok(Value::Boolean(true)),
+            "false" | "0" => Ok(Value::Boolean(false)),
+            _ => Err(format!("expected true or false, got \"{raw}\"")),
+        },
+        Type::List if raw.starts_with('[') => format!("list = {raw}")
+            .parse::<Table>()
+            .ok()
+            .and_then(|mut document| document.remove("list"))
+            .filter(Value::is_array)
+            .ok_or_else(|| format!("expected a list, got \"{raw}\"")),
+        Type::List => Ok(Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|x| !x.is_empty())
+                .map(Value::from)
+                .collect(),
+        )),
+    }
+}
+
+/// Returns the value of a dotted key, e.g. `bench.budget_ms`.
+fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    match key.split_once('.') {
+        Some((section, name)) => table.get(section)?.as_table()?.get(name),
+        None => table.get(key),
+    }
+}
+
+/// Sets the value of a dotted key, creating its section if needed.
+fn insert(table: &mut Table, key: &str, value: Value) {
+    match key.split_once('.') {
+        Some((section, name)) => {
+            let section = table
+                .entry(section)
+                .or_insert_with(|| Value::Table(Table::new()));
+            if let Value::Table(section) = section {
+                section.insert(name.into(), value);
+            }
+        }
+        None => {
+            table.insert(key.into(), value);
+        }
+    }
+}
+
+impl Config {
+    /// Loads `aoc.toml`, or the file in `$AOC_CONFIG`, and applies the environment overrides.
+    /// A missing `aoc.toml` is fine, all settings have defaults.
+    pub fn load() -> Result<Self, Error> {
+        let (path, required) = match env::var_os(PATH_VAR) {
+            Some(path) => (PathBuf::from(path), true),
+            None => (PathBuf::from(FILE_NAME), false),
+        };
+        let document = match fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => None,
+            Err(e) => return Err(Error::Io(path, e)),
+        };
+        Self::from_sources(
+            document.as_deref().map(|content| (content, path.as_path())),
+            |var| env::var(var).ok(),
+        )
+    }
+
+    fn from_sources(
+        file: Option<(&str, &Path)>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self, Error> {
+        let mut document = Table::new();
+        let mut sources = vec![Source::Default; KEYS.len()];
+
+        if let Some((content, path)) = file {
+            let parse_error = |e| Error::Parse(path.to_path_buf(), e);
+            document = content.parse().map_err(parse_error)?;
+            // the file is checked on its own, so its errors are not blamed on an override.
+            Value::Table(document.clone())
+                .try_into::<Config>()
+                .map_err(parse_error)?;
+            for (source, (key, _)) in sources.iter_mut().zip(KEYS) {
+                if lookup(&document, key).is_some() {
+                    *source = Source::File(path.to_path_buf());
+                }
+            }
+        }
+
+        for (source, (key, kind)) in sources.iter_mut().zip(KEYS) {
+            let var = env_var(key);
+            let Some(raw) = env(&var) else {
+                continue;
+            };
+            let invalid = |message| Error::InvalidValue {
+                key: key.to_string(),
+                source: Source::Env(var.clone()),
+                message,
+            };
+            let value = parse_raw(*kind, &raw).map_err(invalid)?;
+            let mut setting = Table::new();
+            insert(&mut setting, key, value.clone());
+            Value::Table(setting)
+                .try_into::<Config>()
+                .map_err(|e| invalid(e.message().to_string()))?;
+            insert(&mut document, key, value);
+            *source = Source::Env(var);
+        }
+
+        let mut config: Config = Value::Table(document)
+            .try_into()
+            .expect("the file and every override are valid");
+        config.sources = KEYS.iter().map(|(key, _)| *key).zip(sources).collect();
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks the settings whose valid values depend on each other or are not told by their type.
+    fn validate(&self) -> Result<(), Error> {
+        let invalid = |key: &str, message: String| Error::InvalidValue {
+            key: key.into(),
+            source: self.source(key).clone(),
+            message,
+        };
+        if let Some(year) = self.year.filter(|year| *year < 2015) {
+            return Err(invalid("year", format!("{year} is not an event year")));
+        }
+        if !self.paths.bin.contains("{day}") {
+            return Err(invalid("paths.bin", "the pattern has no `{day}`".into()));
+        }
+        let (min_samples, max_samples) = (self.bench.min_samples, self.bench.max_samples);
+        if min_samples == 0 || min_samples > max_samples {
+            return Err(invalid(
+                "bench.min_samples",
+                format!("expected between 1 and bench.max_samples ({max_samples})"),
+            ));
+        }
+        Ok(())
+    }
+
+    fn source(&self, key: &str) -> &Source {
+        let (_, source) = self.sources.iter().find(|(k, _)| *k == key).unwrap();
+        source
+    }
+
+    /// Renders the effective configuration as TOML, with the source of every value.
+    pub fn show(&self) -> String {
+        let document = Table::try_from(self).expect("the configuration is valid TOML");
+        let mut out = String::new();
+        let mut section = "";
+        for (key, source) in &self.sources {
+            let (table, name) = key.rsplit_once('.').unwrap_or(("", key));
+            if table != section {
+                out.push_str(&format!("\n[{table}]\n"));
+                section = table;
+            }
+            match lookup(&document, key) {
+                Some(value) => out.push_str(&format!("{name} = {value} # {source}\n")),
+                None => out.push_str(&format!("# {name} is not set\n")),
+            }
+        }
+        out
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The configuration of the current process, loaded on first use. Exits if it is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            eprintln!("Invalid configuration: {e}");
+            process::exit(cli::EXIT_USAGE);
+        })
+    })
+}
+
+/// Applies a `--set <key>=<value>` override by setting the environment variable of the key, so
+/// child processes see it too. Has to be called before the configuration is loaded.
+pub fn set_override(setting: &str) -> Result<(), String> {
+    let (key, value) = setting
+        .split_once('=')
+        .ok_or_else(|| format!("expected <key>=<value>, got \"{setting}\""))?;
+    let key = key.trim();
+    if !KEYS.iter().any(|(k, _)| *k == key) {
+        return Err(format!("unknown key `{key}`"));
+    }
+    env::set_var(env_var(key), value.trim());
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+    use std::time::Duration;
+
+    use super::{env_var, Color, Config, Error};
+    use crate::day;
+    use crate::template::readme_benchmarks::{Column, Sort};
+
+    fn load(file: &str, env: &[(&str, &str)]) -> Result<Config, Error> {
+        Config::from_sources(Some((file, Path::new("aoc.toml"))), |var| {
+            env.iter()
+                .find(|(name, _)| *name == var)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn uses_defaults() {
+        let config = load("", &[]).unwrap();
+        assert_eq!(config.year, None);
+        assert_eq!(
+            config.paths.folder("inputs"),
+            Path::new("data").join("inputs")
+        );
+        assert_eq!(config.paths.bin(day!(3)), Path::new("src/bin/03.rs"));
+        assert_eq!(config.bench.budget, Duration::from_secs(1));
+        assert_eq!(config.bench.columns, vec![]);
+        assert_eq!(config.output.color, Color::Auto);
+    }
+
+    #[test]
+    fn reads_files_and_overrides() {
+        let file = r#"
+year = 2022
+
+[paths]
+data = "aoc"
+inputs = "/tmp/inputs"
+
+[bench]
+budget_ms = 250
+columns = ["stddev", "parse"]
+"#;
+        let config = load(
+            file,
+            &[
+                ("AOC_YEAR", "2023"),
+                ("AOC_BENCH_SORT", "slowest"),
+                ("AOC_BENCH_FORMATS", "csv, json"),
+                ("AOC_OUTPUT_COLOR", "never"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.paths.folder("inputs"), Path::new("/tmp/inputs"));
+        assert_eq!(config.paths.folder("puzzles"), Path::new("aoc/puzzles"));
+        assert_eq!(config.bench.budget, Duration::from_millis(250));
+        assert_eq!(config.bench.columns, vec![Column::Stddev, Column::Parse]);
+        assert_eq!(config.bench.sort, Sort::Slowest);
+        assert_eq!(config.bench.formats.len(), 2);
+        assert_eq!(config.output.color, Color::Never);
+
+        let shown = config.show();
+        assert!(shown.starts_with("year = 2023 # $AOC_YEAR\n"));
+        assert!(shown.contains("\n[paths]\ndata = \"aoc\" # aoc.toml\n"));
+        assert!(shown.contains("# session is not set\n"));
+        assert!(shown.contains("min_samples = 10 # default\n"));
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let error = |file: &str| load(file, &[]).unwrap_err().to_string();
+        assert!(error("[bench]\nbudget = 5")
+            .starts_with("could not parse aoc.toml: unknown field `budget`, expected one of"));
+        assert!(error("year = \"2023\"").contains("invalid type: string \"2023\""));
+        assert!(error("year = 1999").contains("1999 is not an event year"));
+        assert!(error("[paths]\nbin = \"src/main.rs\"").contains("`paths.bin`"));
+        assert!(error("[output]\ncolor = \"pink\"").contains("unknown variant `pink`"));
+        assert!(error("year = ").starts_with("could not parse aoc.toml"));
+        assert!(error("[bench]\nbars = true\n[bench]\nsort = \"day\"")
+            .contains("duplicate key `bench`"));
+
+        let env_error = |var: &str, value: &str| load("", &[(var, value)]).unwrap_err().to_string();
+        assert!(env_error("AOC_BENCH_BARS", "maybe").contains("$AOC_BENCH_BARS"));
+        assert_eq!(
+            env_error("AOC_OUTPUT_COLOR", "pink"),
+            "invalid value of `output.color` in $AOC_OUTPUT_COLOR: \
+             unknown variant `pink`, expected one of `auto`, `always`, `never`"
+        );
+        assert_eq!(env_var("bench.budget_ms"), "AOC_BENCH_BUDGET_MS");
+    }
+}
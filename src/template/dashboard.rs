@@ -0,0 +1,248 @@
+/// State of every day of the calendar as shown by the `dashboard` command, and its rendering.
+///
+/// The state is read from local files only: the solution files, the inputs, the recorded answers,
+/// the outcome of the last `test` and `verify` runs and the benchmark table of the readme.
+use std::fs;
+
+use crate::template::checks::{self, Check};
+use crate::template::{
+    ansi, answers, calendar, config, is_scaffolded, readme_benchmarks, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days, Day};
+
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_REVERSE: &str = "\x1b[7m";
+
+/// Width of a calendar cell, without its border.
+const CELL_WIDTH: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub unlocked: bool,
+    pub scaffolded: bool,
+    /// Whether the input is downloaded, scaffolding creates an empty input file.
+    pub input: bool,
+    /// Outcome of the last `test` run of the day.
+    pub examples: Check,
+    /// Outcome of the last `verify` run of the day.
+    pub answers: Check,
+    /// Stars of the recorded answers.
+    pub stars: usize,
+    /// Timings of both parts in the benchmark table of the readme.
+    pub timings: [Option<String>; 2],
+}
+
+/// Reads the state of every day of `year` from local files.
+pub fn collect(year: u16, now: i64) -> Vec<DayStatus> {
+    let readme = fs::read_to_string("README.md").unwrap_or_default();
+    let timings = readme_benchmarks::read_timings(&readme, Some(year));
+    let inputs = config::get().paths.folder("inputs");
+
+    all_days()
+        .map(|day| {
+            let answers = answers::read(day).unwrap_or_default();
+            let checks = checks::read(day).unwrap_or_default();
+            DayStatus {
+                day,
+                unlocked: calendar::unlock_time(year, day) <= now,
+                scaffolded: is_scaffolded(day),
+                input: fs::metadata(inputs.join(format!("{day}.txt")))
+                    .is_ok_and(|metadata| metadata.len() > 0),
+                examples: checks.examples,
+                answers: checks.answers,
+                stars: usize::from(answers.part_1.is_some())
+                    + usize::from(answers.part_2.is_some()),
+                timings: timings
+                    .iter()
+                    .find(|(d, _)| *d == day)
+                    .map(|(_, timings)| timings.clone())
+                    .unwrap_or_default(),
+            }
+        })
+        .collect()
+}
+
+/// Pads or cuts `s` to `width` characters.
+fn fit(s: &str, width: usize) -> String {
+    let s: String = s.chars().take(width).collect();
+    format!("{s:<width$}")
+}
+
+fn flag(letter: char, check: Check) -> char {
+    match check {
+        Check::Passed => letter,
+        Check::Unknown => '·',
+        Check::Failed => '✖',
+    }
+}
+
+fn passed(ok: bool) -> Check {
+    if ok {
+        Check::Passed
+    } else {
+        Check::Unknown
+    }
+}
+
+/// The three content lines of a calendar cell.
+fn cell(status: &DayStatus) -> [String; 3] {
+    let flags = [
+        flag('S', passed(status.scaffolded)),
+        flag('I', passed(status.input)),
+        flag('E', status.examples),
+        flag('A', status.answers),
+    ];
+    let flags: Vec<String> = flags.iter().map(char::to_string).collect();
+    let timings: Vec<&str> = status
+        .timings
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .collect();
+    [
+        format!(" {}  {}", status.day, "★".repeat(status.stars)),
+        format!(" {}", flags.join(" ")),
+        format!(" {}", timings.join(" ")),
+    ]
+}
+
+fn describe(status: &DayStatus) -> String {
+    let check = |check: Check, passed: &str, failed: &str| match check {
+        Check::Unknown => "not checked".to_string(),
+        Check::Passed => passed.to_string(),
+        Check::Failed => failed.to_string(),
+    };
+    let timing = |part: usize| {
+        status.timings[part]
+            .as_deref()
+            .map_or("-".to_string(), str::to_string)
+    };
+    format!(
+        "{}, {}, examples {}, answers {}, {} stars, benchmark {} / {}",
+        if status.scaffolded {
+            "scaffolded"
+        } else if status.unlocked {
+            "not scaffolded"
+        } else {
+            "locked"
+        },
+        if status.input {
+            "input downloaded"
+        } else {
+            "no input"
+        },
+        check(status.examples, "pass", "fail"),
+        check(status.answers, "verified", "wrong"),
+        status.stars,
+        timing(0),
+        timing(1),
+    )
+}
+
+/// Renders the calendar with `selected` highlighted, the details of the selected day, the keys
+/// and a message of the last action.
+pub fn render(year: u16, days: &[DayStatus], selected: Day, message: &str) -> Vec<String> {
+    let (bold, dim, reverse, reset) = (
+        ansi(ANSI_BOLD),
+        ansi(ANSI_DIM),
+        ansi(ANSI_REVERSE),
+        ansi(ANSI_RESET),
+    );
+    let stars: usize = days.iter().map(|status| status.stars).sum();
+    let mut lines = vec![
+        format!("{bold}Advent of Code {year}{reset}  ★ {stars}/50"),
+        String::new(),
+    ];
+
+    for row in days.chunks(5) {
+        let mut row_lines = vec![String::new(); 5];
+        for status in row {
+            let style = if status.day == selected {
+                reverse
+            } else if !status.unlocked {
+                dim
+            } else {
+                ""
+            };
+            let border = "─".repeat(CELL_WIDTH);
+            let content = cell(status);
+            row_lines[0].push_str(&format!("{style}╭{border}╮{reset}"));
+            for (line, content) in row_lines[1..4].iter_mut().zip(content) {
+                line.push_str(&format!("{style}│{}│{reset}", fit(&content, CELL_WIDTH)));
+            }
+            row_lines[4].push_str(&format!("{style}╰{border}╯{reset}"));
+        }
+        lines.extend(row_lines);
+    }
+
+    if let Some(status) = days.iter().find(|status| status.day == selected) {
+        lines.push(String::new());
+        lines.push(format!(
+            "{bold}Day {}{reset}: {}",
+            status.day,
+            describe(status)
+        ));
+    }
+    lines.push(format!(
+        "{dim}S scaffolded · I input · E examples pass · A answers verified · ★ recorded answers{reset}"
+    ));
+    lines.push(String::new());
+    lines.push(
+        "←↑↓→ move · r run · t test · v verify · p puzzle · 1/2 submit part · q/esc quit"
+            .to_string(),
+    );
+    if !message.is_empty() {
+        lines.push(String::new());
+        lines.push(message.to_string());
+    }
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{cell, describe, fit, DayStatus};
+    use crate::day;
+    use crate::template::checks::Check;
+
+    fn status() -> DayStatus {
+        DayStatus {
+            day: day!(5),
+            unlocked: true,
+            scaffolded: true,
+            input: true,
+            examples: Check::Passed,
+            answers: Check::Failed,
+            stars: 1,
+            timings: [Some("1.2ms".into()), None],
+        }
+    }
+
+    #[test]
+    fn renders_cells() {
+        assert_eq!(cell(&status()), [" 05  ★", " S I E ✖", " 1.2ms"]);
+        let locked = DayStatus {
+            unlocked: false,
+            scaffolded: false,
+            input: false,
+            examples: Check::Unknown,
+            answers: Check::Unknown,
+            stars: 0,
+            timings: [None, None],
+            ..status()
+        };
+        assert_eq!(cell(&locked), [" 05  ", " · · · ·", " "]);
+        assert_eq!(
+            describe(&locked),
+            "locked, no input, examples not checked, answers not checked, 0 stars, benchmark - / -"
+        );
+    }
+
+    #[test]
+    fn fits_text() {
+        assert_eq!(fit("ab", 4), "ab  ");
+        assert_eq!(fit("★★★", 2), "★★");
+    }
+}
@@ -0,0 +1,283 @@
+/// Feeds arbitrary input to solution parts and reports panics and hangs.
+///
+/// Inputs are random bytes, random printable lines, and mutations of the example and real inputs
+/// when they exist in `data/`. Cases run on a worker thread so that a part stuck in an endless
+/// loop is reported as a hang instead of blocking the driver.
+use std::cell::Cell;
+use std::fmt::Display;
+use std::panic;
+use std::sync::{mpsc, OnceLock};
+use std::thread;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::rng::Rng;
+use crate::template::config;
+use crate::Day;
+
+pub const DEFAULT_ITERATIONS: usize = 500;
+const CASE_TIMEOUT: Duration = Duration::from_secs(2);
+const SEED: u64 = 0x00f0_2023;
+
+/// Exit code of `--fuzz` runs when a part panicked.
+pub const EXIT_PANIC: i32 = 3;
+/// Exit code of `--fuzz` runs when a part did not finish in time.
+pub const EXIT_HANG: i32 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Every input was handled without panicking.
+    PanicFree { inputs: usize },
+    /// The part panicked on `input`.
+    Panic { input: String, message: String },
+    /// The part did not return within the timeout on `input`.
+    Hang { input: String },
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::PanicFree { inputs } => write!(f, "✔ panic-free ({inputs} inputs)"),
+            Outcome::Panic { message, .. } => write!(f, "✖ panicked: {message}"),
+            Outcome::Hang { .. } => write!(f, "✖ no result after {CASE_TIMEOUT:?}"),
+        }
+    }
+}
+
+/// Inputs worth mutating: the example and the real input of `day`, when present.
+pub fn corpus(day: Day) -> Vec<String> {
+    ["examples", "inputs"]
+        .iter()
+        .filter_map(|folder| {
+            let path = config::get()
+                .paths
+                .folder(folder)
+                .join(format!("{day}.txt"));
+            fs::read_to_string(path).ok()
+        })
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Generates a fuzzing input, possibly derived from one of the `corpus` inputs.
+pub fn generate(rng: &mut Rng, corpus: &[String]) -> String {
+    const PRINTABLE: &[u8] =
+        b"0123456789 abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ:;,.=|-()#*\n";
+
+    let random_bytes = |rng: &mut Rng| {
+        let len = rng.range(0, 64) as usize;
+        let bytes: Vec<u8> = (0..len).map(|_| rng.next_u64() as u8).collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    let random_text = |rng: &mut Rng| {
+        let len = rng.range(0, 200) as usize;
+        (0..len)
+            .map(|_| *rng.choose(PRINTABLE) as char)
+            .collect::<String>()
+    };
+
+    match rng.range(0, 3) {
+        0 => random_bytes(rng),
+        1 => random_text(rng),
+        _ if corpus.is_empty() => random_text(rng),
+        _ => {
+            let seed = rng.choose(corpus);
+            mutate(rng, seed, PRINTABLE)
+        }
+    }
+}
+
+fn mutate(rng: &mut Rng, seed: &str, alphabet: &[u8]) -> String {
+    let mut lines: Vec<String> = seed.lines().map(Into::into).collect();
+    for _ in 0..rng.range(1, 4) {
+        if lines.is_empty() {
+            break;
+        }
+        let line = rng.range(0, lines.len() as u64 - 1) as usize;
+        match rng.range(0, 5) {
+            0 => {
+                lines.remove(line);
+            }
+            1 => {
+                let copy = lines[line].clone();
+                lines.insert(line, copy);
+            }
+            2 => {
+                let cut = rng.range(0, lines[line].len() as u64) as usize;
+                let cut = (0..=cut)
+                    .rev()
+                    .find(|i| lines[line].is_char_boundary(*i))
+                    .unwrap_or(0);
+                lines[line].truncate(cut);
+            }
+            3 => lines[line].push_str(&u64::MAX.to_string()),
+            _ => {
+                let mut bytes = lines[line].clone().into_bytes();
+                if !bytes.is_empty() {
+                    let at = rng.range(0, bytes.len() as u64 - 1) as usize;
+                    bytes[at] = *rng.choose(alphabet);
+                }
+                lines[line] = String::from_utf8_lossy(&bytes).into_owned();
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".into())
+}
+
+thread_local! {
+    /// Whether the thread runs fuzz cases, whose panics are expected and reported as outcomes.
+    static FUZZING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Installs a panic hook that is quiet on threads running fuzz cases and defers to the previous
+/// hook on all others. The hook is process-wide, so it is installed once and never swapped back,
+/// which would race with parts fuzzed in parallel, e.g. by libtest.
+fn install_panic_hook() {
+    static INSTALLED: OnceLock<()> = OnceLock::new();
+    INSTALLED.get_or_init(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !FUZZING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// A thread running the cases of a part one after another.
+struct Worker {
+    cases: mpsc::Sender<String>,
+    results: mpsc::Receiver<Result<(), String>>,
+}
+
+impl Worker {
+    fn spawn<T: 'static>(func: fn(&str) -> T) -> Self {
+        let (cases, case_receiver) = mpsc::channel::<String>();
+        let (result_sender, results) = mpsc::channel();
+        thread::spawn(move || {
+            FUZZING.with(|fuzzing| fuzzing.set(true));
+            // ends when the driver drops the worker.
+            for case in case_receiver {
+                let result = panic::catch_unwind(|| {
+                    func(&case);
+                });
+                if result_sender
+                    .send(result.map_err(|e| panic_message(&*e)))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Self { cases, results }
+    }
+
+    /// Runs a case, [`None`] if it did not finish in time. The worker is stuck on the case then
+    /// and has to be replaced.
+    fn run(&self, input: String) -> Option<Result<(), String>> {
+        self.cases.send(input).ok()?;
+        self.results.recv_timeout(CASE_TIMEOUT).ok()
+    }
+}
+
+/// Runs `func` against `iterations` generated inputs, stopping at the first panic or hang.
+pub fn fuzz_part<T>(func: fn(&str) -> T, corpus: &[String], iterations: usize) -> Outcome
+where
+    T: 'static,
+{
+    install_panic_hook();
+    let mut rng = Rng::new(SEED);
+    // a panicking case unwinds into the worker, which keeps running the next cases.
+    let worker = Worker::spawn(func);
+
+    for _ in 0..iterations {
+        let input = generate(&mut rng, corpus);
+        match worker.run(input.clone()) {
+            Some(Ok(())) => {}
+            Some(Err(message)) => return Outcome::Panic { input, message },
+            // fuzzing stops at a hang, the stuck worker is left behind instead of replaced.
+            None => return Outcome::Hang { input },
+        }
+    }
+    Outcome::PanicFree { inputs: iterations }
+}
+
+/// Writes a failing input to `data/fuzz/<day>-part<part>.txt`.
+pub fn save_failure(day: Day, part: u8, input: &str) -> io::Result<String> {
+    let folder = config::get().paths.data.join("fuzz");
+    fs::create_dir_all(&folder)?;
+    let path = folder.join(format!("{day}-part{part}.txt"));
+    fs::write(&path, input)?;
+    Ok(path.display().to_string())
+}
+
+/// Entry point of the libtest driver generated by the `solution!` macro.
+pub fn assert_panic_free<A: 'static, B: 'static>(
+    day: Day,
+    part_one: fn(&str) -> A,
+    part_two: fn(&str) -> B,
+    iterations: usize,
+) {
+    let corpus = corpus(day);
+    let outcomes = [
+        fuzz_part(part_one, &corpus, iterations),
+        fuzz_part(part_two, &corpus, iterations),
+    ];
+
+    for (part, outcome) in outcomes.iter().enumerate() {
+        if let Outcome::Panic { input, .. } | Outcome::Hang { input } = outcome {
+            panic!("Part {}: {outcome}\n--- input ---\n{input}", part + 1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fuzz_part, Outcome};
+
+    fn strict(input: &str) -> u32 {
+        input.trim().parse().expect("should be a number")
+    }
+
+    fn lenient(input: &str) -> Option<u32> {
+        input.trim().parse().ok()
+    }
+
+    fn stuck(_: &str) -> usize {
+        loop {
+            std::thread::park();
+        }
+    }
+
+    #[test]
+    fn reports_panics() {
+        let outcome = fuzz_part(strict, &["12".into()], 100);
+        assert!(
+            matches!(&outcome, Outcome::Panic { message, .. } if message.starts_with("should be a number")),
+            "{outcome:?}"
+        );
+    }
+
+    #[test]
+    fn reports_panic_free_parts() {
+        assert_eq!(
+            fuzz_part(lenient, &[], 100),
+            Outcome::PanicFree { inputs: 100 }
+        );
+    }
+
+    #[test]
+    fn reports_hangs() {
+        assert!(matches!(fuzz_part(stuck, &[], 1), Outcome::Hang { .. }));
+    }
+}
@@ -0,0 +1,488 @@
+/// Renders the puzzle descriptions of the Advent of Code website, the `<article class="day-desc">`
+/// blocks of a day page, to Markdown or to formatted terminal output.
+///
+/// Only the handful of elements descriptions use is supported: headings, paragraphs, emphasis,
+/// inline code, code blocks, lists and links. Other tags are dropped and their text is kept.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const BASE_URL: &str = "https://adventofcode.com";
+const ANSI_CODE: &str = "\x1b[36m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    /// Text with ANSI escape codes for emphasis and code, and terminal hyperlinks.
+    Terminal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    Close(String),
+    Text(String),
+}
+
+impl Token {
+    fn attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            Token::Open { attributes, .. } => attributes
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Decodes character references, e.g. `&lt;` or `&#39;`. Unknown references are kept as they are.
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                entity => {
+                    let code = entity.strip_prefix('#')?;
+                    let code = match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => code.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_attributes(s: &str) -> Vec<(String, String)> {
+    let mut attributes = vec![];
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_lowercase();
+        rest = rest[name_end..].trim_start();
+
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, remaining) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let end = after[1..].find(quote).map_or(after.len(), |i| i + 1);
+                    (&after[1..end], after.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after.find(char::is_whitespace).unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            rest = remaining.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+
+        if !name.is_empty() {
+            attributes.push((name, value));
+        }
+    }
+    attributes
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+        }
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_lowercase()));
+        } else if !tag.starts_with('!') && !tag.starts_with('?') {
+            let tag = tag.strip_suffix('/').unwrap_or(tag);
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            tokens.push(Token::Open {
+                name: tag[..name_end].to_lowercase(),
+                attributes: parse_attributes(&tag[name_end..]),
+            });
+        }
+    }
+    tokens
+}
+
+/// Returns the content of the `<article class="day-desc">` blocks of a day page, i.e. the
+/// description of part one and, once it is unlocked, of part two.
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        rest = &rest[start..];
+        let Some(open_end) = rest.find('>') else {
+            break;
+        };
+        let is_description = rest[..open_end].contains("day-desc");
+        let content = &rest[open_end + 1..];
+        let close = content.find("</article>").unwrap_or(content.len());
+        if is_description {
+            articles.push(&content[..close]);
+        }
+        rest = &content[close..];
+    }
+    articles
+}
+
+/// Inline elements that are open at the current position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Inline {
+    Emphasis,
+    Code,
+    Link(String),
+}
+
+struct Renderer {
+    format: Format,
+    out: String,
+    /// The block that is being rendered, e.g. a paragraph.
+    line: String,
+    inline: Vec<Inline>,
+    pre: bool,
+    list_depth: usize,
+}
+
+impl Renderer {
+    fn new(format: Format) -> Self {
+        Self {
+            format,
+            out: String::new(),
+            line: String::new(),
+            inline: vec![],
+            pre: false,
+            list_depth: 0,
+        }
+    }
+
+    fn in_code(&self) -> bool {
+        self.pre || self.inline.contains(&Inline::Code)
+    }
+
+    /// Escape codes that restore the styles of all open inline elements.
+    fn terminal_styles(&self) -> String {
+        self.inline
+            .iter()
+            .map(|inline| match inline {
+                Inline::Emphasis => ANSI_BOLD,
+                Inline::Code => ANSI_CODE,
+                Inline::Link(_) => ANSI_UNDERLINE,
+            })
+            .collect()
+    }
+
+    /// Ends the current block, followed by a blank line if `paragraph` is set.
+    fn flush(&mut self, paragraph: bool) {
+        let line = self.line.trim_end();
+        if !line.trim().is_empty() {
+            self.out.push_str(line);
+            self.out.push('\n');
+        }
+        self.line.clear();
+        if paragraph && !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.pre {
+            self.line.push_str(text);
+            return;
+        }
+        // whitespace is collapsed, and dropped at the start of a block.
+        let space = |line: &mut String| {
+            if !line.is_empty() && !line.ends_with([' ', '\n']) {
+                line.push(' ');
+            }
+        };
+        if text.starts_with(char::is_whitespace) {
+            space(&mut self.line);
+        }
+        for (i, word) in text.split_whitespace().enumerate() {
+            if i > 0 {
+                self.line.push(' ');
+            }
+            if self.format == Format::Markdown && !self.in_code() {
+                for c in word.chars() {
+                    if matches!(c, '\\' | '*' | '_' | '[' | ']' | '`') {
+                        self.line.push('\\');
+                    }
+                    self.line.push(c);
+                }
+            } else {
+                self.line.push_str(word);
+            }
+        }
+        if text.ends_with(char::is_whitespace) {
+            space(&mut self.line);
+        }
+    }
+
+    fn open_inline(&mut self, inline: Inline) {
+        // code blocks are formatted as a whole.
+        if self.pre && inline == Inline::Code {
+            return;
+        }
+        match (self.format, &inline) {
+            (Format::Markdown, Inline::Emphasis) if !self.in_code() => self.line.push('*'),
+            (Format::Markdown, Inline::Code) if !self.in_code() => self.line.push('`'),
+            (Format::Markdown, Inline::Link(_)) => self.line.push('['),
+            (Format::Markdown, _) => {}
+            (Format::Terminal, Inline::Link(href)) => {
+                // OSC 8 hyperlink, shown as plain underlined text by terminals without support.
+                self.line
+                    .push_str(&format!("\x1b]8;;{href}\x1b\\{ANSI_UNDERLINE}"));
+            }
+            (Format::Terminal, Inline::Emphasis) => self.line.push_str(ANSI_BOLD),
+            (Format::Terminal, Inline::Code) => self.line.push_str(ANSI_CODE),
+        }
+        self.inline.push(inline);
+    }
+
+    fn close_inline(&mut self, matches: impl Fn(&Inline) -> bool) {
+        let Some(index) = self.inline.iter().rposition(matches) else {
+            return;
+        };
+        let inline = self.inline.remove(index);
+        match (self.format, inline) {
+            (Format::Markdown, Inline::Emphasis) if !self.in_code() => self.line.push('*'),
+            (Format::Markdown, Inline::Code) if !self.in_code() => self.line.push('`'),
+            (Format::Markdown, Inline::Link(href)) => self.line.push_str(&format!("]({href})")),
+            (Format::Markdown, _) => {}
+            (Format::Terminal, inline) => {
+                self.line.push_str(ANSI_RESET);
+                if matches!(inline, Inline::Link(_)) {
+                    self.line.push_str("\x1b]8;;\x1b\\");
+                }
+                let styles = self.terminal_styles();
+                self.line.push_str(&styles);
+            }
+        }
+    }
+
+    fn open(&mut self, token: &Token, name: &str) {
+        match name {
+            "h2" | "p" => self.flush(true),
+            "pre" => {
+                self.flush(true);
+                self.pre = true;
+                if self.format == Format::Markdown {
+                    self.line.push_str("```\n");
+                }
+            }
+            "ul" | "ol" => {
+                self.flush(self.list_depth == 0);
+                self.list_depth += 1;
+            }
+            "li" => {
+                self.flush(false);
+                let indent = "  ".repeat(self.list_depth.saturating_sub(1));
+                self.line.push_str(&format!("{indent}- "));
+            }
+            "br" => self.line.push('\n'),
+            "em" => self.open_inline(Inline::Emphasis),
+            "code" => self.open_inline(Inline::Code),
+            "a" => {
+                let href = token.attribute("href").unwrap_or_default();
+                let href = if href.starts_with('/') {
+                    format!("{BASE_URL}{href}")
+                } else {
+                    href.to_string()
+                };
+                self.open_inline(Inline::Link(href));
+            }
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "h2" => {
+                let heading = std::mem::take(&mut self.line);
+                let heading = heading.trim();
+                self.line = match self.format {
+                    Format::Markdown => format!("## {heading}"),
+                    Format::Terminal => format!("{ANSI_BOLD}{heading}{ANSI_RESET}"),
+                };
+                self.flush(true);
+            }
+            "p" => self.flush(true),
+            "pre" => {
+                let code = std::mem::take(&mut self.line);
+                let code = code.trim_end_matches(['\n', ' ']);
+                self.line = match self.format {
+                    Format::Markdown => format!("{code}\n```"),
+                    Format::Terminal => code
+                        .lines()
+                        .map(|line| format!("    {line}"))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                };
+                self.pre = false;
+                self.flush(true);
+            }
+            "ul" | "ol" => {
+                self.list_depth = self.list_depth.saturating_sub(1);
+                self.flush(self.list_depth == 0);
+            }
+            "li" => self.flush(false),
+            "em" => self.close_inline(|i| *i == Inline::Emphasis),
+            "code" => self.close_inline(|i| *i == Inline::Code),
+            "a" => self.close_inline(|i| matches!(i, Inline::Link(_))),
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush(true);
+        let out = self.out.trim_end();
+        format!("{out}\n")
+    }
+}
+
+/// Renders HTML, typically the content of an article returned by [`articles`].
+pub fn render(html: &str, format: Format) -> String {
+    let mut renderer = Renderer::new(format);
+    for token in tokenize(html) {
+        match &token {
+            Token::Open { name, .. } => renderer.open(&token, name),
+            Token::Close(name) => renderer.close(name),
+            Token::Text(text) => renderer.text(text),
+        }
+    }
+    renderer.finish()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles, decode_entities, render, Format};
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with <em>global snow production</em>, see <a href="/2023/about">about</a>.</p>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul>
+<li>In <code>1abc2</code>, the value is <code><em>12</em></code>.</li>
+<li>Done &amp; dusted: <span title="Easter egg">x &lt; y</span></li>
+</ul>
+<p>What is the sum?</p>
+</article>
+<p>Your puzzle answer was <code>142</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>It's <em>spelled</em> out.</p></article>
+</main></body></html>"#;
+
+    #[test]
+    fn finds_articles() {
+        let articles = articles(PAGE);
+        assert_eq!(articles.len(), 2);
+        assert!(articles[0].starts_with("<h2>--- Day 1"));
+        assert!(articles[1].ends_with("out.</p>"));
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &#39;c&#x27;"),
+            "a <b> & 'c'"
+        );
+        assert_eq!(decode_entities("AT&T &unknown; &"), "AT&T &unknown; &");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let expected = "## --- Day 1: Trebuchet?! ---
+
+Something is wrong with *global snow production*, see [about](https://adventofcode.com/2023/about).
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+- In `1abc2`, the value is `12`.
+- Done & dusted: x < y
+
+What is the sum?
+";
+        assert_eq!(render(articles(PAGE)[0], Format::Markdown), expected);
+        assert_eq!(
+            render(articles(PAGE)[1], Format::Markdown),
+            "## --- Part Two ---\n\nIt's *spelled* out.\n"
+        );
+    }
+
+    #[test]
+    fn escapes_markdown() {
+        assert_eq!(
+            render("<p>a*b_c <code>d*e</code></p>", Format::Markdown),
+            "a\\*b\\_c `d*e`\n"
+        );
+    }
+
+    #[test]
+    fn renders_terminal() {
+        let out = render(articles(PAGE)[0], Format::Terminal);
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "\x1b[1m--- Day 1: Trebuchet?! ---\x1b[0m");
+        assert!(lines[2].contains("\x1b[1mglobal snow production\x1b[0m"));
+        assert!(lines[2].contains("\x1b]8;;https://adventofcode.com/2023/about\x1b\\"));
+        assert_eq!(lines[6], "    1abc2");
+        // styles of enclosing elements are restored after nested ones.
+        assert!(lines[9].ends_with("\x1b[36m\x1b[1m12\x1b[0m\x1b[36m\x1b[0m."));
+    }
+}
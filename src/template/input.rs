@@ -0,0 +1,242 @@
+/// Normalisation of puzzle inputs before they reach a solution.
+///
+/// Inputs come from different places - downloaded files, examples pasted on Windows, indented
+/// string literals in tests - and differ in line endings, indentation and surrounding blank lines.
+/// A [`Policy`] makes these differences explicit. Days get their input verbatim unless they pick
+/// one through the `solution!` macro, e.g. `advent_of_code::solution!(1, input = Policy::TRIMMED)`.
+/// Byte-oriented days skip normalisation and read the file as is, possibly through a
+/// [`MappedFile`].
+use std::error::Error;
+use std::fmt::Display;
+use std::io;
+use std::ops::Deref;
+use std::path::Path;
+
+/// Which normalisations to apply to an input, see [`Policy::normalize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Policy {
+    /// Converts `\r\n` line endings to `\n`.
+    pub crlf_to_lf: bool,
+    /// Removes trailing whitespace of every line, as well as leading and trailing blank lines.
+    pub trim: bool,
+    /// Strips the indentation shared by all non-blank lines.
+    pub dedent: bool,
+    /// Rejects inputs containing non-ASCII characters.
+    pub ascii_only: bool,
+}
+
+impl Policy {
+    /// Passes the input through unchanged, used by days that do not pick a policy.
+    pub const VERBATIM: Self = Self {
+        crlf_to_lf: false,
+        trim: false,
+        dedent: false,
+        ascii_only: false,
+    };
+
+    /// Fixes line endings and surrounding whitespace.
+    pub const TRIMMED: Self = Self {
+        crlf_to_lf: true,
+        trim: true,
+        dedent: false,
+        ascii_only: false,
+    };
+
+    /// Also accepts examples written as indented string literals in tests.
+    pub const INLINE: Self = Self {
+        dedent: true,
+        ..Self::TRIMMED
+    };
+
+    /// Applies every normalisation of the policy to `input`.
+    pub fn normalize(&self, input: &str) -> Result<String, InputError> {
+        if self.ascii_only {
+            check_ascii(input)?;
+        }
+
+        let mut lines: Vec<&str> = input.split('\n').collect();
+        if self.crlf_to_lf {
+            for line in &mut lines {
+                *line = line.strip_suffix('\r').unwrap_or(line);
+            }
+        }
+        if self.trim {
+            for line in &mut lines {
+                *line = line.trim_end();
+            }
+            let first = lines.iter().position(|l| !l.is_empty()).unwrap_or(0);
+            let last = lines
+                .iter()
+                .rposition(|l| !l.is_empty())
+                .map_or(0, |i| i + 1);
+            lines = lines[first..last.max(first)].to_vec();
+        }
+        if self.dedent {
+            let indent = lines
+                .iter()
+                .filter(|l| !l.trim().is_empty())
+                .map(|l| l.len() - l.trim_start_matches([' ', '\t']).len())
+                .min()
+                .unwrap_or(0);
+            for line in &mut lines {
+                *line = line.get(indent..).unwrap_or("");
+            }
+        }
+
+        Ok(lines.join("\n"))
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::VERBATIM
+    }
+}
+
+fn check_ascii(input: &str) -> Result<(), InputError> {
+    for (line_index, line) in input.lines().enumerate() {
+        if let Some((column, found)) = line.chars().enumerate().find(|(_, c)| !c.is_ascii()) {
+            return Err(InputError::NonAscii {
+                line: line_index + 1,
+                column: column + 1,
+                found,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputError {
+    /// A character outside of ASCII, at a 1-based position.
+    NonAscii {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NonAscii {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "non-ASCII character {found:?} (U+{:04X}) at line {line}, column {column}",
+                *found as u32
+            ),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/* -------------------------------------------------------------------------- */
+
+/// The bytes of an input file. With the `mmap` feature, the file is memory-mapped rather than
+/// read, so that large inputs are paged in lazily and never copied.
+pub struct MappedFile {
+    #[cfg(feature = "mmap")]
+    map: Option<memmap2::Mmap>,
+    #[cfg(not(feature = "mmap"))]
+    bytes: Vec<u8>,
+}
+
+impl MappedFile {
+    #[cfg(feature = "mmap")]
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // empty files cannot be mapped on every platform.
+        if file.metadata()?.len() == 0 {
+            return Ok(Self { map: None });
+        }
+        // SAFETY: puzzle inputs are not modified while a solution runs.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Ok(Self { map: Some(map) })
+    }
+
+    #[cfg(not(feature = "mmap"))]
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            bytes: std::fs::read(path)?,
+        })
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    #[cfg(feature = "mmap")]
+    fn deref(&self) -> &Self::Target {
+        self.map.as_deref().unwrap_or_default()
+    }
+
+    #[cfg(not(feature = "mmap"))]
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+// Not part of the public API, converts generated fuzzing inputs for the `solution!` macro.
+#[doc(hidden)]
+pub fn as_str(input: &str) -> &str {
+    input
+}
+
+// Not part of the public API, converts generated fuzzing inputs for the `solution!` macro.
+#[doc(hidden)]
+pub fn as_bytes(input: &str) -> &[u8] {
+    input.as_bytes()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputError, Policy};
+
+    #[test]
+    fn verbatim_keeps_input() {
+        let input = "\r\n  a \r\nb\r\n";
+        assert_eq!(Policy::VERBATIM.normalize(input).unwrap(), input);
+    }
+
+    #[test]
+    fn trimmed_fixes_line_endings_and_whitespace() {
+        let input = "\r\n\r\n  a  \r\nb\t\r\n\r\n";
+        assert_eq!(Policy::TRIMMED.normalize(input).unwrap(), "  a\nb");
+        assert_eq!(Policy::TRIMMED.normalize("\n \n").unwrap(), "");
+    }
+
+    #[test]
+    fn inline_strips_common_indentation() {
+        let input = "\n    Card 1: 1 | 2\n\n      Card 2: 3 | 4\n    ";
+        assert_eq!(
+            Policy::INLINE.normalize(input).unwrap(),
+            "Card 1: 1 | 2\n\n  Card 2: 3 | 4"
+        );
+    }
+
+    #[test]
+    fn rejects_non_ascii() {
+        let policy = Policy {
+            ascii_only: true,
+            ..Policy::TRIMMED
+        };
+        assert_eq!(
+            policy.normalize("ab\ncé"),
+            Err(InputError::NonAscii {
+                line: 2,
+                column: 2,
+                found: 'é'
+            })
+        );
+        assert_eq!(
+            policy.normalize("ab\ncé").unwrap_err().to_string(),
+            "non-ASCII character 'é' (U+00E9) at line 2, column 2"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+/// Private leaderboards, fetched with the [`aoc_client`] and cached in `data/leaderboard/`.
+///
+/// The website asks to request a leaderboard at most once every 15 minutes, so a cached copy
+/// younger than that is used instead of fetching it again.
+use std::collections::HashMap;
+use std::fmt::{Display, Write as _};
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::template::{ansi, aoc_client, calendar, config, ANSI_BOLD, ANSI_RESET};
+use crate::{all_days, Day};
+
+pub const CACHE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug)]
+pub enum Error {
+    Client(aoc_client::ClientError),
+    Cache(io::Error),
+    Parser(String),
+    /// `--offline` was requested but nothing is cached.
+    NotCached(PathBuf),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Client(e) => write!(f, "{e}"),
+            Error::Cache(e) => write!(f, "could not access the cache: {e}"),
+            Error::Parser(e) => write!(f, "invalid leaderboard: {e}"),
+            Error::NotCached(path) => write!(f, "no cached leaderboard at \"{}\".", path.display()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
+/// A member of a leaderboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Unix timestamps of the stars of both parts, indexed by day - 1.
+    pub completions: [[Option<i64>; 2]; 25],
+}
+
+impl Member {
+    /// The name of the member, or how the website shows anonymous users.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn completion(&self, day: Day) -> [Option<i64>; 2] {
+        self.completions[usize::from(day.into_inner()) - 1]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub id: u64,
+    pub owner_id: u64,
+    pub year: u16,
+    /// Members, ordered by local score.
+    pub members: Vec<Member>,
+}
+
+/// A leaderboard as returned by the website, with the fields that are used.
+#[derive(Debug, Deserialize)]
+struct Response {
+    owner_id: u64,
+    /// The year of the event, as a string.
+    event: String,
+    members: HashMap<String, MemberResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MemberResponse {
+    id: u64,
+    name: Option<String>,
+    #[serde(default)]
+    stars: u32,
+    #[serde(default)]
+    local_score: u32,
+    /// Stars by day and part, both numbers as strings.
+    #[serde(default)]
+    completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: i64,
+}
+
+impl Leaderboard {
+    /// Reads a leaderboard from the JSON returned by the website.
+    pub fn from_json(id: u64, json: &str) -> Result<Self, Error> {
+        let response: Response = serde_json::from_str(json)?;
+        let year = response
+            .event
+            .parse()
+            .map_err(|_| Error::Parser(format!("invalid event \"{}\"", response.event)))?;
+
+        let mut members: Vec<Member> = response
+            .members
+            .into_values()
+            .map(|member| {
+                let mut completions = [[None; 2]; 25];
+                for (day, parts) in &member.completion_day_level {
+                    let Some(index) = day.parse::<usize>().ok().filter(|d| (1..=25).contains(d))
+                    else {
+                        continue;
+                    };
+                    for (part, completion) in completions[index - 1].iter_mut().enumerate() {
+                        *completion = parts
+                            .get(&(part + 1).to_string())
+                            .map(|star| star.get_star_ts);
+                    }
+                }
+                Member {
+                    id: member.id,
+                    name: member.name,
+                    stars: member.stars,
+                    local_score: member.local_score,
+                    completions,
+                }
+            })
+            .collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Self {
+            id,
+            owner_id: response.owner_id,
+            year,
+            members,
+        })
+    }
+
+    /// Exports the leaderboard with timestamps and deltas of the days members collected stars on.
+    pub fn to_json(&self) -> Value {
+        let members: Vec<Value> = self
+            .members
+            .iter()
+            .map(|member| {
+                let days: Vec<Value> = all_days()
+                    .filter_map(|day| {
+                        let [part_1, part_2] = member.completion(day);
+                        part_1.or(part_2)?;
+                        Some(json!({
+                            "day": day,
+                            "part_1": part_1,
+                            "part_2": part_2,
+                            "delta": part_1.zip(part_2).map(|(a, b)| b - a),
+                        }))
+                    })
+                    .collect();
+                json!({
+                    "id": member.id,
+                    "name": member.name,
+                    "stars": member.stars,
+                    "local_score": member.local_score,
+                    "days": days,
+                })
+            })
+            .collect();
+        json!({
+            "id": self.id,
+            "owner_id": self.owner_id,
+            "year": self.year,
+            "members": members,
+        })
+    }
+
+    /// Days that are unlocked at `now`.
+    fn unlocked_days(&self, now: i64) -> Vec<Day> {
+        all_days()
+            .filter(|&day| calendar::unlock_time(self.year, day) <= now)
+            .collect()
+    }
+
+    /// Renders members with their local score and a star per day, like the website:
+    /// `★` for both parts, `☆` for part one only.
+    pub fn render(&self, now: i64) -> String {
+        let days = self.unlocked_days(now);
+        let mut out = String::new();
+
+        // rank and score come first, e.g. `  1)   123 `.
+        let offset = 4 + 1 + 5 + 1;
+        for digit in [|d: u8| d / 10, |d: u8| d % 10] {
+            let header: String = days
+                .iter()
+                .map(|day| match digit(day.into_inner()) {
+                    0 if day.into_inner() < 10 => ' ',
+                    d => char::from(b'0' + d),
+                })
+                .collect();
+            let line = format!("{:offset$}{header}", "");
+            let _ = writeln!(out, "{}", line.trim_end());
+        }
+
+        for (rank, member) in self.members.iter().enumerate() {
+            let grid: String = days
+                .iter()
+                .map(|&day| match member.completion(day) {
+                    [Some(_), Some(_)] => '★',
+                    [Some(_), None] | [None, Some(_)] => '☆',
+                    [None, None] => '·',
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "{:>3}) {:>5} {grid} {} {}{}{}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                ansi(ANSI_BOLD),
+                member.display_name(),
+                ansi(ANSI_RESET)
+            );
+        }
+        out
+    }
+
+    /// Renders the completion times of a day relative to its unlock and the delta between parts,
+    /// fastest part two first.
+    pub fn render_day(&self, day: Day) -> String {
+        let unlock = calendar::unlock_time(self.year, day);
+        let mut members: Vec<_> = self
+            .members
+            .iter()
+            .filter(|m| m.completion(day)[0].is_some())
+            .collect();
+        members.sort_by_key(|m| {
+            let [part_1, part_2] = m.completion(day);
+            (part_2.unwrap_or(i64::MAX), part_1)
+        });
+
+        let time = |ts: Option<i64>| ts.map_or("-".into(), calendar::format_duration);
+        let mut out = format!(
+            "{}Day {day}{}, unlocked {}\n",
+            ansi(ANSI_BOLD),
+            ansi(ANSI_RESET),
+            calendar::format_timestamp(unlock)
+        );
+        let _ = writeln!(
+            out,
+            "     {:>14} {:>14} {:>14}  Name",
+            "Part 1", "Part 2", "Delta"
+        );
+        for (rank, member) in members.iter().enumerate() {
+            let [part_1, part_2] = member.completion(day);
+            let _ = writeln!(
+                out,
+                "{:>3}) {:>14} {:>14} {:>14}  {}",
+                rank + 1,
+                time(part_1.map(|ts| ts - unlock)),
+                time(part_2.map(|ts| ts - unlock)),
+                time(part_1.zip(part_2).map(|(a, b)| b - a)),
+                member.display_name()
+            );
+        }
+        if members.is_empty() {
+            out.push_str("Nobody collected a star yet.\n");
+        }
+        out
+    }
+}
+
+/// Where a leaderboard was loaded from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Fetched,
+    /// The cache, which is this old.
+    Cache(Duration),
+}
+
+pub fn get_cache_path(year: u16, id: u64) -> PathBuf {
+    config::get()
+        .paths
+        .data
+        .join("leaderboard")
+        .join(format!("{year}-{id}.json"))
+}
+
+fn cache_age(path: &PathBuf) -> Option<Duration> {
+    fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()
+}
+
+/// Loads a leaderboard from the cache if it is recent enough or `offline` is set, and fetches it
+/// otherwise. The raw response is cached, so it can be passed to `cargo stars --leaderboard`.
+pub fn load(year: u16, id: u64, offline: bool) -> Result<(Leaderboard, Source), Error> {
+    let path = get_cache_path(year, id);
+    let age = cache_age(&path);
+
+    let (content, source) = match age {
+        Some(age) if offline || age < CACHE_INTERVAL => (
+            fs::read_to_string(&path).map_err(Error::Cache)?,
+            Source::Cache(age),
+        ),
+        None if offline => return Err(Error::NotCached(path)),
+        _ => {
+            let content = aoc_client::get(&format!("/{year}/leaderboard/private/view/{id}.json"))
+                .map_err(Error::Client)?;
+            if let Some(folder) = path.parent() {
+                fs::create_dir_all(folder).map_err(Error::Cache)?;
+            }
+            fs::write(&path, &content).map_err(Error::Cache)?;
+            (content, Source::Fetched)
+        }
+    };
+
+    let leaderboard = Leaderboard::from_json(id, &content)?;
+    Ok((leaderboard, source))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Leaderboard;
+    use crate::day;
+    use crate::template::calendar;
+
+    fn get_mock_leaderboard() -> Leaderboard {
+        let unlock = calendar::unlock_time(2023, day!(2));
+        let json = format!(
+            r#"{{"owner_id": 7, "event": "2023", "members": {{
+                "7": {{"id": 7, "name": "owner", "stars": 1, "local_score": 3, "completion_day_level": {{
+                    "2": {{"1": {{"get_star_ts": {}, "star_index": 1}}}}
+                }}}},
+                "9": {{"id": 9, "name": null, "stars": 2, "local_score": 4, "completion_day_level": {{
+                    "2": {{"1": {{"get_star_ts": {}}}, "2": {{"get_star_ts": {}}}}}
+                }}}}
+            }}}}"#,
+            unlock + 600,
+            unlock + 3723,
+            unlock + 4000
+        );
+        Leaderboard::from_json(1, &json).unwrap()
+    }
+
+    #[test]
+    fn reads_leaderboards() {
+        let leaderboard = get_mock_leaderboard();
+        assert_eq!(leaderboard.year, 2023);
+        assert_eq!(leaderboard.owner_id, 7);
+        let ids: Vec<_> = leaderboard.members.iter().map(|m| m.id).collect();
+        assert_eq!(ids, [9, 7]);
+        assert_eq!(leaderboard.members[0].display_name(), "(anonymous user #9)");
+        assert!(leaderboard.members[1].completion(day!(2))[0].is_some());
+        assert_eq!(leaderboard.members[1].completion(day!(2))[1], None);
+    }
+
+    #[test]
+    fn renders_leaderboards() {
+        let leaderboard = get_mock_leaderboard();
+        let now = calendar::unlock_time(2023, day!(3));
+        let out = leaderboard.render(now);
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].ends_with(" 123"));
+        assert!(lines[2].starts_with("  1)     4 ·★· 2 "));
+        assert!(lines[3].starts_with("  2)     3 ·☆· 1 "));
+    }
+
+    #[test]
+    fn renders_days() {
+        let out = get_mock_leaderboard().render_day(day!(2));
+        let lines: Vec<_> = out.lines().collect();
+        assert!(lines[0].ends_with("2023-12-02 05:00:00 UTC"));
+        assert!(lines[2].contains("01:02:03       01:06:40       00:04:37"));
+        assert!(lines[3].contains("00:10:00              -              -"));
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = get_mock_leaderboard().to_json().to_string();
+        assert!(json.contains(r#""name":null"#));
+        assert!(json.contains(r#""delta":277"#));
+        assert!(json.contains(r#""part_2":null,"delta":null"#));
+    }
+}
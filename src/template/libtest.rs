@@ -0,0 +1,206 @@
+/// Arguments that make a test harness print its outcome in the stable, human-readable format: a
+/// `test <name> ... <outcome>` line per test, followed by the captured output of failed tests and
+/// a `test result:` line once all tests ran.
+pub const ARGS: [&str; 4] = ["--format", "pretty", "--color", "never"];
+
+/// Outcome of a single test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+/// Number of tests per outcome.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+}
+
+impl Counts {
+    pub fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Passed => self.passed += 1,
+            Outcome::Failed => self.failed += 1,
+            Outcome::Ignored => self.ignored += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: Counts) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.ignored += other.ignored;
+    }
+
+    pub fn total(&self) -> usize {
+        self.passed + self.failed + self.ignored
+    }
+}
+
+/// A test that failed, with the output it captured, e.g. the message of its panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub name: String,
+    pub stdout: String,
+}
+
+/// Outcome of the tests of a solution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Tests of part 1 and part 2.
+    pub parts: [Counts; 2],
+    /// Tests that belong to neither part.
+    pub other: Counts,
+    pub failures: Vec<Failure>,
+    /// Whether the harness reported the end of the run, it did not if it crashed.
+    pub finished: bool,
+}
+
+impl Report {
+    /// Reads the output a test harness printed. Lines that are not about a test, e.g. output of
+    /// tests that run with `--nocapture`, are skipped.
+    pub fn parse(output: &str) -> Self {
+        let mut report = Report::default();
+        // index of the failed test whose captured output is being read.
+        let mut captured: Option<usize> = None;
+
+        for line in output.lines() {
+            if line.starts_with("test result: ") {
+                report.finished = true;
+                captured = None;
+            } else if let Some(header) = line.strip_prefix("---- ") {
+                let name = header.strip_suffix(" stdout ----").unwrap_or(header);
+                captured = report.failures.iter().position(|f| f.name == name);
+            } else if line == "failures:" || line == "successes:" {
+                captured = None;
+            } else if let Some(index) = captured {
+                let stdout = &mut report.failures[index].stdout;
+                stdout.push_str(line);
+                stdout.push('\n');
+            } else if let Some((name, outcome)) = parse_test_line(line) {
+                match part(name) {
+                    Some(part) => report.parts[part as usize - 1].add(outcome),
+                    None => report.other.add(outcome),
+                }
+                if outcome == Outcome::Failed {
+                    report.failures.push(Failure {
+                        name: name.to_string(),
+                        stdout: String::new(),
+                    });
+                }
+            }
+        }
+        report
+    }
+
+    /// Counts of all tests.
+    pub fn total(&self) -> Counts {
+        let mut total = self.other;
+        for counts in self.parts {
+            total.merge(counts);
+        }
+        total
+    }
+}
+
+/// Reads a `test <name> ... <outcome>` line.
+fn parse_test_line(line: &str) -> Option<(&str, Outcome)> {
+    let (name, outcome) = line.strip_prefix("test ")?.split_once(" ... ")?;
+    let outcome = match outcome {
+        "ok" => Outcome::Passed,
+        "FAILED" => Outcome::Failed,
+        _ if outcome.starts_with("ignored") => Outcome::Ignored,
+        _ => return None,
+    };
+    Some((name, outcome))
+}
+
+/// The part a test belongs to by its name, without the path of its module: `test_part_one` or
+/// `part_1` belong to part 1, the others, e.g. differential tests of helpers, to neither part.
+pub fn part(name: &str) -> Option<u8> {
+    let name = name.rsplit("::").next().unwrap_or(name);
+    if name.contains("part_one") || name.contains("part_1") {
+        Some(1)
+    } else if name.contains("part_two") || name.contains("part_2") {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{part, Counts, Failure, Report};
+
+    #[test]
+    fn attributes_tests_to_parts() {
+        assert_eq!(part("tests::test_part_one"), Some(1));
+        assert_eq!(part("tests::part_2_with_overlaps"), Some(2));
+        assert_eq!(part("tests::test_split_range"), None);
+        assert_eq!(part("part_two::tests::test_split_range"), None);
+    }
+
+    #[test]
+    fn parses_output() {
+        let output = "
+running 4 tests
+test fuzz_parts_are_panic_free ... ignored, fuzzing takes a while, run with `--ignored`
+test tests::test_part_one ... ok
+debug output
+test tests::test_part_two ... FAILED
+test tests::test_map_range ... ok
+
+failures:
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' panicked at src/bin/05.rs:10:9:
+assertion failed
+
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+
+        let report = Report::parse(output);
+        assert_eq!(
+            report.parts,
+            [
+                Counts {
+                    passed: 1,
+                    ..Counts::default()
+                },
+                Counts {
+                    failed: 1,
+                    ..Counts::default()
+                },
+            ]
+        );
+        assert_eq!(
+            report.other,
+            Counts {
+                passed: 1,
+                failed: 0,
+                ignored: 1,
+            }
+        );
+        assert_eq!(
+            report.failures,
+            [Failure {
+                name: "tests::test_part_two".into(),
+                stdout: "\nthread 'tests::test_part_two' panicked at src/bin/05.rs:10:9:\n\
+                         assertion failed\n\n\n"
+                    .into(),
+            }]
+        );
+        assert!(report.finished);
+        assert_eq!(report.total().total(), 4);
+        assert!(!Report::parse("thread 'main' panicked").finished);
+    }
+}
@@ -1,36 +1,171 @@
-use crate::Day;
+use crate::{Day, DaySelection, DaySet};
+use std::path::PathBuf;
 use std::{env, fs};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod calendar;
+pub mod cargo;
+pub mod checks;
+pub mod cli;
 pub mod commands;
+pub mod config;
+pub mod dashboard;
+pub mod fuzz;
+pub mod html;
+pub mod input;
+pub mod leaderboard;
+pub mod libtest;
 pub mod readme_benchmarks;
+pub mod readme_stars;
 pub mod runner;
+pub mod stats;
+#[cfg(unix)]
+pub mod terminal;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns `code`, or nothing if colours are disabled with `output.color`.
+pub fn ansi(code: &'static str) -> &'static str {
+    if config::get().output.color.enabled() {
+        code
+    } else {
+        ""
+    }
+}
+
+/// Resolves a selection of days: `latest` is the last day of the configured or current event that
+/// unlocked, and a day is solved once answers to both parts are recorded.
+pub fn select_days(selection: DaySelection) -> DaySet {
+    let now = calendar::now();
+    let year = config::get()
+        .year
+        .unwrap_or_else(|| calendar::event_year(now));
+    let latest = calendar::latest_unlock(year, now);
+    selection.resolve(latest, |day| {
+        answers::read(day).is_ok_and(|answers| answers.part_1.is_some() && answers.part_2.is_some())
+    })
+}
+
+/// Whether the solution file of a day exists.
+pub fn is_scaffolded(day: Day) -> bool {
+    config::get().paths.bin(day).exists()
+}
+
+fn input_path(folder: &str, day: Day) -> PathBuf {
+    let cwd = env::current_dir().unwrap();
+    cwd.join(config::get().paths.folder(folder))
+        .join(format!("{day}.txt"))
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    let f = fs::read_to_string(input_path(folder, day));
+    f.expect("could not open input file")
+}
+
+/// Reads a text file to a string and normalises it according to `policy`.
+#[must_use]
+pub fn read_input(folder: &str, day: Day, policy: input::Policy) -> String {
+    policy
+        .normalize(&read_file(folder, day))
+        .unwrap_or_else(|e| {
+            panic!(
+                "invalid input file {}: {e}",
+                input_path(folder, day).display()
+            )
+        })
+}
+
+/// Reads a file to bytes, without any normalisation.
+#[must_use]
+pub fn read_bytes(folder: &str, day: Day) -> Vec<u8> {
+    let f = fs::read(input_path(folder, day));
+    f.expect("could not open input file")
+}
+
+/// Maps a file into memory, see [`input::MappedFile`].
+#[must_use]
+pub fn map_file(folder: &str, day: Day) -> input::MappedFile {
+    let f = input::MappedFile::open(&input_path(folder, day));
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// The second argument picks how the input is loaded and what the parts receive:
+///  - nothing or `input = <policy>`: a `&str` normalised according to an [`input::Policy`],
+///    [`input::Policy::VERBATIM`] if omitted. The policy is available as the constant `INPUT`.
+///  - `bytes`: the raw bytes of the file as `&[u8]`.
+///  - `mmap`: the raw bytes of the file as `&[u8]`, memory-mapped with the `mmap` feature.
+///
+/// Parts are called with a borrowed input, which is never copied between benchmark samples.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
+        advent_of_code::solution!(
+            $day,
+            input = advent_of_code::template::input::Policy::VERBATIM
+        );
+    };
+    ($day:expr, input = $policy:expr) => {
+        /// How inputs of the current day are normalised.
+        const INPUT: advent_of_code::template::input::Policy = $policy;
+
+        advent_of_code::solution!(
+            @define $day,
+            advent_of_code::template::read_input("inputs", DAY, INPUT),
+            advent_of_code::template::input::as_str
+        );
+    };
+    ($day:expr, bytes) => {
+        advent_of_code::solution!(
+            @define $day,
+            advent_of_code::template::read_bytes("inputs", DAY),
+            advent_of_code::template::input::as_bytes
+        );
+    };
+    ($day:expr, mmap) => {
+        advent_of_code::solution!(
+            @define $day,
+            advent_of_code::template::map_file("inputs", DAY),
+            advent_of_code::template::input::as_bytes
+        );
+    };
+    (@define $day:expr, $read:expr, $from_str:path) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            if let Some(iterations) = fuzz_iterations() {
+                fuzz_parts(
+                    DAY,
+                    |input| part_one($from_str(input)),
+                    |input| part_two($from_str(input)),
+                    iterations,
+                );
+            }
+            let input = $read;
+            run_part(part_one, &*input, DAY, 1);
+            run_part(part_two, &*input, DAY, 2);
+        }
+
+        /// Feeds arbitrary input to both parts, run with `cargo test --bin <day> -- --ignored`.
+        #[cfg(test)]
+        #[test]
+        #[ignore = "fuzzing takes a while, run with `--ignored`"]
+        fn fuzz_parts_are_panic_free() {
+            advent_of_code::template::fuzz::assert_panic_free(
+                DAY,
+                |input| part_one($from_str(input)),
+                |input| part_two($from_str(input)),
+                advent_of_code::template::fuzz::DEFAULT_ITERATIONS,
+            );
         }
     };
 }
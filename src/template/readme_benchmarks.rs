@@ -1,7 +1,13 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::path::PathBuf;
 use std::{fs, io};
 
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::template::config;
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -18,26 +24,164 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// Measurements of a part, parsed from the output of its solution.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartTiming {
+    /// Average duration as printed by the runner, e.g. `74.13ns`.
+    pub time: String,
+    pub nanos: f64,
+    pub samples: Option<u128>,
+    /// Standard deviation of the samples, e.g. `1.2µs`.
+    pub stddev: Option<String>,
+    /// Allocation stats, only present when allocations are counted.
+    pub allocations: Option<String>,
+    /// Duration of the `parse` span, only present when profiled.
+    pub parse: Option<String>,
+}
+
+impl From<&str> for PartTiming {
+    fn from(time: &str) -> Self {
+        Self {
+            time: time.into(),
+            ..Self::default()
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    fn parts(&self) -> [Option<&PartTiming>; 2] {
+        [self.part_1.as_ref(), self.part_2.as_ref()]
+    }
+}
+
+/// Optional columns of the benchmark table, shown for both parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Samples,
+    Stddev,
+    #[serde(rename = "memory", alias = "allocs", alias = "allocations")]
+    #[value(name = "memory", alias = "allocs", alias = "allocations")]
+    Allocations,
+    Parse,
+}
+
+impl Column {
+    fn name(self) -> &'static str {
+        match self {
+            Column::Samples => "samples",
+            Column::Stddev => "stddev",
+            Column::Allocations => "memory",
+            Column::Parse => "parse",
+        }
+    }
+
+    fn value(self, part: &PartTiming) -> Option<String> {
+        match self {
+            Column::Samples => part.samples.map(|x| x.to_string()),
+            Column::Stddev => part.stddev.clone(),
+            Column::Allocations => part.allocations.clone(),
+            Column::Parse => part.parse.clone(),
+        }
+    }
+}
+
+/// Order of the days in the benchmark table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Sort {
+    #[default]
+    Day,
+    Slowest,
+}
+
+/// Files the benchmarks can be written to besides the readme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    Csv,
+    Json,
+    Html,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::Html => "html",
+        }
+    }
+}
+
+/// How the benchmark table is laid out and where it is written, set with flags on `all`.
+#[derive(Debug, Clone, Default)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Adds a bar showing the cost of each day relative to the slowest one.
+    pub bars: bool,
+    /// Keeps one section per year in the readme, instead of a single table.
+    pub by_year: bool,
+    /// Files written to `data/benchmarks.<extension>` in addition to the readme.
+    pub formats: Vec<Format>,
+}
+
+impl TableOptions {
+    /// Whether solutions have to run with `--parse-time` to fill the requested columns.
+    pub fn needs_parse_time(&self) -> bool {
+        self.columns.contains(&Column::Parse)
+    }
+
+    /// Requested columns, plus allocations whenever they were measured.
+    fn columns(&self, timings: &[Timings]) -> Vec<Column> {
+        let mut columns = self.columns.clone();
+        let measured_allocations = timings
+            .iter()
+            .flat_map(Timings::parts)
+            .any(|part| part.is_some_and(|p| p.allocations.is_some()));
+        if measured_allocations && !columns.contains(&Column::Allocations) {
+            columns.push(Column::Allocations);
+        }
+        columns.dedup();
+        columns
+    }
+
+    fn sorted(&self, mut timings: Vec<Timings>) -> Vec<Timings> {
+        if self.sort == Sort::Slowest {
+            timings.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+        }
+        timings
+    }
+}
+
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(super) pos_start: usize,
+    pub(super) pos_end: usize,
 }
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    let path = config::get().paths.bin(day);
+    if path.is_relative() {
+        format!("./{}", path.display())
+    } else {
+        path.display().to_string()
+    }
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates the content between two occurrences of `marker`, or a single one if the table was
+/// not written yet.
+pub(super) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -58,54 +202,358 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Renders `value` relative to `max` as a bar of eighth blocks, ten characters at most.
+fn bar(value: f64, max: f64) -> String {
+    const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    if max <= 0.0 {
+        return String::new();
+    }
+    let eighths = ((value / max).clamp(0.0, 1.0) * 80.0).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    bar.push_str(EIGHTHS[eighths % 8]);
+    bar
+}
+
+/// Headers of the part columns, e.g. `Part 1`, `Part 2`, `Part 1 stddev`, `Part 2 stddev`.
+fn part_headers(columns: &[Column]) -> Vec<String> {
+    let mut headers = vec!["Part 1".to_string(), "Part 2".to_string()];
+    for column in columns {
+        headers.push(format!("Part 1 {}", column.name()));
+        headers.push(format!("Part 2 {}", column.name()));
+    }
+    headers
+}
+
+/// Values of the part columns, in the order of [`part_headers`].
+fn part_cells(timing: &Timings, columns: &[Column]) -> Vec<Option<String>> {
+    let mut cells: Vec<_> = timing
+        .parts()
+        .iter()
+        .map(|part| part.map(|p| p.time.clone()))
+        .collect();
+    for column in columns {
+        cells.extend(
+            timing
+                .parts()
+                .iter()
+                .map(|p| p.and_then(|p| column.value(p))),
+        );
+    }
+    cells
+}
+
+fn markdown_table(timings: &[Timings], total_millis: f64, options: &TableOptions) -> Vec<String> {
+    let columns = options.columns(timings);
+    let max_nanos = timings.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
+
+    let mut headers = vec!["Day".to_string()];
+    headers.extend(part_headers(&columns));
+    if options.bars {
+        headers.push("Cost".into());
+    }
+    let alignments: Vec<_> = (0..headers.len())
+        .map(|i| if i == 2 { ":---: " } else { ":---:" })
+        .collect();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", headers.join(" | ")),
+        format!("| {} |", alignments.join(" | ")),
     ];
 
-    for timing in timings {
+    for timing in options.sorted(timings.to_vec()) {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+        for (i, cell) in part_cells(&timing, &columns).into_iter().enumerate() {
+            cells.push(match cell {
+                // timings are shown as code, like in the console output.
+                Some(cell) if i < 2 => format!("`{cell}`"),
+                Some(cell) => cell,
+                None if i < 2 => "`-`".into(),
+                None => "-".into(),
+            });
+        }
+        if options.bars {
+            cells.push(bar(timing.total_nanos, max_nanos));
+        }
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines
+}
+
+/// Keeps the sections of other years found in `existing`, e.g. `### 2022` and its table.
+fn other_year_sections(existing: &str, year: u16) -> Vec<(u16, String)> {
+    let mut sections: Vec<(u16, String)> = vec![];
+    for line in existing.lines() {
+        if let Some(section_year) = line
+            .strip_prefix("### ")
+            .and_then(|x| x.trim().parse::<u16>().ok())
+        {
+            sections.push((section_year, String::new()));
+        }
+        if let Some((_, content)) = sections.last_mut() {
+            if line != MARKER {
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+    }
+    sections.retain(|(section_year, _)| *section_year != year);
+    sections
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+    year: Option<u16>,
+    existing: &str,
+) -> String {
+    let header = format!("{prefix} Benchmarks");
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    match year.filter(|_| options.by_year) {
+        Some(year) => {
+            let mut sections = other_year_sections(existing, year);
+            let table = markdown_table(&timings, total_millis, options).join("\n");
+            sections.push((year, format!("{prefix}# {year}\n\n{table}\n")));
+            sections.sort_by_key(|(year, _)| std::cmp::Reverse(*year));
+            let sections: Vec<_> = sections.iter().map(|(_, s)| s.trim_end()).collect();
+            lines.push(sections.join("\n\n"));
+        }
+        None => lines.extend(markdown_table(&timings, total_millis, options)),
+    }
 
+    lines.push(MARKER.into());
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+    year: Option<u16>,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let existing = s[positions.pos_start..positions.pos_end].to_string();
+    let table = construct_table("##", timings, total_millis, options, year, &existing);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(
+    timings: Vec<Timings>,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        timings,
+        total_millis,
+        options,
+        config::get().year,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/// Reads the timings of both parts per day back from the benchmark table of a readme. With a
+/// table per year, only the one of `year` is read.
+pub fn read_timings(readme: &str, year: Option<u16>) -> Vec<(Day, [Option<String>; 2])> {
+    let Ok(positions) = locate_table(readme, MARKER) else {
+        return vec![];
+    };
+    let mut section = None;
+    let mut timings = vec![];
+
+    for line in readme[positions.pos_start..positions.pos_end].lines() {
+        if let Some(section_year) = line.strip_prefix("### ") {
+            section = section_year.trim().parse::<u16>().ok();
+            continue;
+        }
+        if section.is_some() && section != year {
+            continue;
+        }
+        let Some(row) = line.strip_prefix("| [Day ") else {
+            continue;
+        };
+        let mut cells = row.split(" | ");
+        let Some(day) = cells
+            .next()
+            .and_then(|cell| cell.split(']').next())
+            .and_then(|day| day.parse().ok())
+        else {
+            continue;
+        };
+        let mut part = || {
+            let cell = cells.next()?.trim().trim_end_matches('|').trim();
+            let time = cell.trim_matches('`');
+            (time != "-").then(|| time.to_string())
+        };
+        timings.push((day, [part(), part()]));
+    }
+    timings
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn to_csv(timings: &[Timings], options: &TableOptions) -> String {
+    let columns = options.columns(timings);
+    let mut headers = vec!["day".to_string()];
+    headers.extend(
+        part_headers(&columns)
+            .iter()
+            .map(|h| h.to_lowercase().replace(' ', "_")),
+    );
+    headers.push("total_nanos".into());
+
+    let mut lines = vec![headers.join(",")];
+    for timing in options.sorted(timings.to_vec()) {
+        let mut fields = vec![timing.day.into_inner().to_string()];
+        fields.extend(
+            part_cells(&timing, &columns)
+                .iter()
+                .map(|cell| csv_field(cell.as_deref().unwrap_or_default())),
+        );
+        fields.push(format!("{}", timing.total_nanos));
+        lines.push(fields.join(","));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn to_json(timings: &[Timings], total_millis: f64, options: &TableOptions) -> String {
+    let part = |part: Option<&PartTiming>| {
+        part.map(|p| {
+            json!({
+                "time": p.time,
+                "nanos": p.nanos,
+                "samples": p.samples,
+                "stddev": p.stddev,
+                "allocations": p.allocations,
+                "parse": p.parse,
+            })
+        })
+    };
+    let days: Vec<Value> = options
+        .sorted(timings.to_vec())
+        .iter()
+        .map(|timing| {
+            json!({
+                "day": timing.day,
+                "part_1": part(timing.part_1.as_ref()),
+                "part_2": part(timing.part_2.as_ref()),
+                "total_nanos": timing.total_nanos,
+            })
+        })
+        .collect();
+
+    let document = json!({
+        "year": config::get().year,
+        "total_millis": total_millis,
+        "days": days,
+    });
+    format!("{document:#}\n")
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn to_html(timings: &[Timings], total_millis: f64, options: &TableOptions) -> String {
+    let columns = options.columns(timings);
+    let max_nanos = timings.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
+
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Benchmarks</title>\n\
+         <style>body { font-family: sans-serif; } td, th { padding: 0.2em 0.8em; text-align: right; } \
+         .bar { color: #2a7; text-align: left; }</style>\n</head>\n<body>\n<h1>Benchmarks</h1>\n<table>\n<tr><th>Day</th>",
+    );
+    for header in part_headers(&columns) {
+        html.push_str(&format!("<th>{}</th>", html_escape(&header)));
+    }
+    if options.bars {
+        html.push_str("<th>Cost</th>");
+    }
+    html.push_str("</tr>\n");
+
+    for timing in options.sorted(timings.to_vec()) {
+        html.push_str(&format!("<tr><td>{}</td>", timing.day.into_inner()));
+        for cell in part_cells(&timing, &columns) {
+            html.push_str(&format!(
+                "<td>{}</td>",
+                html_escape(cell.as_deref().unwrap_or("-"))
+            ));
+        }
+        if options.bars {
+            html.push_str(&format!(
+                "<td class=\"bar\">{}</td>",
+                bar(timing.total_nanos, max_nanos)
+            ));
+        }
+        html.push_str("</tr>\n");
+    }
+
+    html.push_str(&format!(
+        "</table>\n<p><strong>Total: {total_millis:.2}ms</strong></p>\n</body>\n</html>\n"
+    ));
+    html
+}
+
+/// Writes the benchmarks to `data/benchmarks.<extension>` for every requested format.
+pub fn write_outputs(
+    timings: &[Timings],
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = vec![];
+    for format in &options.formats {
+        let content = match format {
+            Format::Csv => to_csv(timings, options),
+            Format::Json => to_json(timings, total_millis, options),
+            Format::Html => to_html(timings, total_millis, options),
+        };
+        let path = config::get()
+            .paths
+            .data
+            .join(format!("benchmarks.{}", format.extension()));
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        fs::write(&path, content)?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{
+        bar, read_timings, to_csv, to_json, update_content, Column, Sort, TableOptions, Timings,
+        MARKER,
+    };
     use crate::day;
+    use clap::ValueEnum;
+
+    fn defaults() -> TableOptions {
+        TableOptions::default()
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -134,28 +582,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &defaults(), None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &defaults(), None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &defaults(), None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &defaults(), None).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &defaults(), None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,7 +611,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &defaults(), None).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -183,4 +631,122 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1.as_mut().unwrap().allocations = Some("3 allocs, 1.5 KiB peak".into());
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &defaults(), None).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 memory | Part 2 memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | 3 allocs, 1.5 KiB peak | - |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_options() {
+        let mut timings = get_mock_timings();
+        let part = timings[1].part_2.as_mut().unwrap();
+        part.samples = Some(10);
+        part.stddev = Some("1.2ms".into());
+        let options = TableOptions {
+            columns: vec![Column::Samples, Column::Stddev],
+            sort: Sort::Slowest,
+            bars: true,
+            ..defaults()
+        };
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options, None).unwrap();
+        let lines: Vec<_> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Part 1 | Part 2 | Part 1 samples | Part 2 samples | Part 1 stddev | Part 2 stddev | Cost |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | - | - | - | - | ██████████ |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | 10 | - | 1.2ms | ███████▊ |"
+        );
+        assert!(lines[7].starts_with("| [Day 1]"));
+    }
+
+    #[test]
+    fn keeps_sections_of_other_years() {
+        let options = TableOptions {
+            by_year: true,
+            ..defaults()
+        };
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &options, Some(2022)).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &options, Some(2023)).unwrap();
+        update_content(&mut s, get_mock_timings(), 100.0, &options, Some(2023)).unwrap();
+        assert_eq!(s.matches("### 2023").count(), 1);
+        assert_eq!(s.matches("### 2022").count(), 1);
+        assert!(s.find("### 2023").unwrap() < s.find("### 2022").unwrap());
+        assert!(s.contains("**Total: 100.00ms**\n\n### 2022"));
+        assert!(s.contains("**Total: 190.00ms**\n<!---"));
+    }
+
+    #[test]
+    fn reads_timings_back() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &defaults(), None).unwrap();
+        let timings = read_timings(&s, Some(2023));
+        assert_eq!(timings.len(), 3);
+        assert_eq!(
+            timings[0],
+            (day!(1), [Some("10ms".into()), Some("20ms".into())])
+        );
+
+        let options = TableOptions {
+            by_year: true,
+            ..defaults()
+        };
+        let mut timings = get_mock_timings();
+        timings[0].part_2 = None;
+        update_content(&mut s, timings, 190.0, &options, Some(2022)).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings()[1..].to_vec(),
+            100.0,
+            &options,
+            Some(2023),
+        )
+        .unwrap();
+        assert_eq!(
+            read_timings(&s, Some(2022))[0],
+            (day!(1), [Some("10ms".into()), None])
+        );
+        assert_eq!(read_timings(&s, Some(2023)).len(), 2);
+    }
+
+    #[test]
+    fn renders_bars() {
+        assert_eq!(bar(0.0, 10.0), "");
+        assert_eq!(bar(5.0, 10.0), "█████");
+        assert_eq!(bar(1.0, 10.0), "█");
+        assert_eq!(bar(0.3, 10.0), "▎");
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        assert!(Column::from_str("typo", false).is_err());
+        assert_eq!(Column::from_str("allocs", false), Ok(Column::Allocations));
+    }
+
+    #[test]
+    fn exports_csv_and_json() {
+        let timings = get_mock_timings();
+        let csv = to_csv(&timings, &defaults());
+        assert_eq!(csv.lines().next(), Some("day,part_1,part_2,total_nanos"));
+        assert_eq!(csv.lines().nth(1), Some("1,10ms,20ms,30000000000"));
+        let json = to_json(&timings, 190.0, &defaults());
+        assert!(json.contains("\"total_millis\": 190"));
+        assert!(json.contains("\"time\": \"40ms\""));
+    }
 }
@@ -0,0 +1,221 @@
+/// Module that updates the readme with the stars collected per day.
+/// The table has the layout of `aoc-readme-stars`, whose marker it fills, and is located and
+/// replaced the same way as the benchmark table.
+use std::fs;
+
+use serde_json::Value;
+
+use crate::template::answers;
+use crate::template::readme_benchmarks::{locate_table, Error};
+use crate::{all_days, Day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Stars collected on a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl DayStars {
+    pub fn count(&self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
+/// Stars of the days that have answers recorded in `data/answers/`.
+pub fn from_answers() -> Result<Vec<DayStars>, Error> {
+    let mut stars = vec![];
+    for day in all_days() {
+        let answers = answers::read(day)?;
+        stars.push(DayStars {
+            day,
+            part_1: answers.part_1.is_some(),
+            part_2: answers.part_2.is_some(),
+        });
+    }
+    Ok(stars)
+}
+
+/// Stars of a member of a private leaderboard, as returned by
+/// `https://adventofcode.com/{year}/leaderboard/private/view/{id}.json`.
+/// Without `user_id`, the owner of the leaderboard is picked.
+pub fn from_leaderboard(leaderboard: &Value, user_id: Option<u64>) -> Result<Vec<DayStars>, Error> {
+    let user_id = user_id
+        .or_else(|| leaderboard.get("owner_id")?.as_u64())
+        .ok_or_else(|| Error::Parser("Leaderboard has no owner, pass a user id.".into()))?;
+
+    let member = leaderboard
+        .get("members")
+        .and_then(|members| members.get(user_id.to_string()))
+        .ok_or_else(|| Error::Parser(format!("User {user_id} is not on the leaderboard.")))?;
+
+    let levels = member.get("completion_day_level");
+    Ok(all_days()
+        .map(|day| {
+            let level = levels.and_then(|l| l.get(day.into_inner().to_string()));
+            DayStars {
+                day,
+                part_1: level.and_then(|l| l.get("1")).is_some(),
+                part_2: level.and_then(|l| l.get("2")).is_some(),
+            }
+        })
+        .collect())
+}
+
+/// Combines stars of several sources, a part counts if any source has its star.
+pub fn merge(a: &[DayStars], b: &[DayStars]) -> Vec<DayStars> {
+    all_days()
+        .map(|day| {
+            let find = |stars: &[DayStars]| stars.iter().find(|s| s.day == day).copied();
+            let (a, b) = (find(a), find(b));
+            DayStars {
+                day,
+                part_1: a.is_some_and(|s| s.part_1) || b.is_some_and(|s| s.part_1),
+                part_2: a.is_some_and(|s| s.part_2) || b.is_some_and(|s| s.part_2),
+            }
+        })
+        .collect()
+}
+
+fn day_link(day: Day, year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("https://adventofcode.com/{year}/day/{}", day.into_inner()),
+        None => super::readme_benchmarks::get_path_for_bin(day),
+    }
+}
+
+fn construct_table(prefix: &str, stars: &[DayStars], year: Option<u16>) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+    let star = |collected: bool| if collected { "⭐" } else { " " };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    // like `aoc-readme-stars`, days without any star are left out.
+    for day in stars.iter().filter(|s| s.count() > 0) {
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} |",
+            day.day.into_inner(),
+            day_link(day.day, year),
+            star(day.part_1),
+            star(day.part_2)
+        ));
+    }
+
+    let total: usize = stars.iter().map(DayStars::count).sum();
+    lines.push(String::new());
+    lines.push(format!("**Total: {total} ⭐**"));
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, stars: &[DayStars], year: Option<u16>) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", stars, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(stars: &[DayStars], year: Option<u16>) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, stars, year)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use serde_json::Value;
+
+    use super::{from_leaderboard, merge, update_content, DayStars, MARKER};
+    use crate::{all_days, day};
+
+    fn get_mock_stars() -> Vec<DayStars> {
+        all_days()
+            .map(|day| DayStars {
+                day,
+                part_1: day <= day!(2),
+                part_2: day == day!(1),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, &get_mock_stars(), Some(2023)).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |   |",
+            "",
+            "**Total: 3 ⭐**",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_existing_stars() {
+        let mut s = format!("foo\n{}{}\nbar", MARKER, MARKER);
+        update_content(&mut s, &get_mock_stars(), None).unwrap();
+        update_content(&mut s, &get_mock_stars(), None).unwrap();
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## Results").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | ⭐ | ⭐ |"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_stars(), None).unwrap();
+    }
+
+    #[test]
+    fn reads_leaderboard() {
+        let leaderboard: Value = serde_json::from_str(
+            r#"{"owner_id": 7, "event": "2023", "members": {
+                "7": {"id": 7, "stars": 3, "completion_day_level": {
+                    "3": {"1": {"get_star_ts": 1701580000, "star_index": 1}},
+                    "4": {"1": {"get_star_ts": 1701670000, "star_index": 2}, "2": {"get_star_ts": 1701680000, "star_index": 3}}
+                }},
+                "9": {"id": 9, "stars": 0, "completion_day_level": {}}
+            }}"#,
+        )
+        .unwrap();
+
+        let stars = from_leaderboard(&leaderboard, None).unwrap();
+        assert_eq!(stars.iter().map(DayStars::count).sum::<usize>(), 3);
+        assert!(stars[2].part_1 && !stars[2].part_2);
+        assert!(stars[3].part_1 && stars[3].part_2);
+
+        let merged = merge(&stars, &get_mock_stars());
+        assert_eq!(merged.iter().map(DayStars::count).sum::<usize>(), 6);
+
+        let stars = from_leaderboard(&leaderboard, Some(9)).unwrap();
+        assert_eq!(stars.iter().map(DayStars::count).sum::<usize>(), 0);
+        assert!(from_leaderboard(&leaderboard, Some(1)).is_err());
+    }
+}
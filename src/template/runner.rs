@@ -1,81 +1,259 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
-use std::fmt::Display;
+use crate::allocations::{self, Stats};
+use crate::answer::Answer;
+use crate::template::cli::{self, Solution, SolutionFlags};
+use crate::template::{ansi, answers, aoc_cli, config, fuzz, stats, ANSI_ITALIC, ANSI_RESET};
+use crate::{memo, overflow, profile, recorder, viz, Day};
+use clap::Parser;
+use std::hint::black_box;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Runs a part, prints its result and measurement and checks the result against the recorded
+/// answer. Parts can return anything that converts into an [`Answer`].
+pub fn run_part<I: ?Sized, T: Into<Answer>>(
+    func: impl Fn(&I) -> Option<T>,
+    input: &I,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, measurement) = run_timed(func, input, |result| {
+        let result = result.map(Into::into);
+        print_result(&result, &part_str, "");
+        result
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_measurement(&measurement));
+    if let Some(stats) = measurement.memo {
+        println!("        memo: {stats}");
+    }
+    let truncations = overflow::take_truncations();
+    output_recording(day, part);
+    output_profile(day, part);
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        check_truncation(&result, &truncations);
+        verify_result(&result, day, part);
+        if let Some(Ok(output)) = submit_result(result, day, part) {
+            record_submission(&output, day, part, measurement.duration);
+        }
     }
 }
 
+/// What the runner measured for a part.
+struct Measurement {
+    /// Average duration of the samples.
+    duration: Duration,
+    samples: u128,
+    /// Standard deviation of the samples, when benchmarked.
+    stddev: Option<Duration>,
+    allocations: Option<Stats>,
+    /// Lookups of memos during the first execution, counted in debug builds.
+    memo: Option<memo::Stats>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///  2. in release, the function is benched (approx. `bench.budget_ms` of execution time, at least `bench.min_samples` samples.)
+///
+/// Visualisations requested with `--viz` are produced by an extra execution before the first one, as they print and
+/// write files, which must not be timed. Recordings requested with `--record` are only produced by the first execution.
+/// Allocations are counted during the first execution too, when the `count_allocations` feature is enabled,
+/// and so are spans when profiling is requested with `--profile` or the parse time with `--parse-time`.
+/// Conversions with [`overflow::narrow`] are checked during the first execution as well, in debug builds.
+/// Memos are cleared before every execution, so samples do not read values cached by the previous one.
+/// The result of the first execution is passed to `hook` before benchmarking, which returns what the runner keeps of it.
+fn run_timed<I: ?Sized, T, U>(
+    func: impl Fn(&I) -> T,
+    input: &I,
+    hook: impl FnOnce(T) -> U,
+) -> (U, Measurement) {
+    if flags().viz {
+        viz::set_enabled(true);
+        black_box(func(input));
+        viz::set_enabled(false);
+    }
+
+    recorder::set_enabled(flags().record.is_some());
+    profile::set_enabled(flags().profile || args().parse_time);
+    overflow::set_enabled(true);
+    memo::clear_all();
+    memo::take_stats();
     let timer = Instant::now();
-    let result = func(input.clone());
+    let (result, allocations) = allocations::measure(|| func(input));
     let base_time = timer.elapsed();
+    let memo = memo::take_stats();
+    recorder::set_enabled(false);
+    profile::set_enabled(false);
+    overflow::set_enabled(false);
 
-    hook(&result);
+    let result = hook(result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (duration, samples, stddev) = if flags().time {
+        let (duration, samples, stddev) = bench(func, input, &base_time);
+        (duration, samples, Some(stddev))
     } else {
-        (base_time, 1)
+        (base_time, 1, None)
     };
 
-    (result, run.0, run.1)
+    let measurement = Measurement {
+        duration,
+        samples,
+        stddev,
+        allocations,
+        memo,
+    };
+    (result, measurement)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: ?Sized, T>(
+    func: impl Fn(&I) -> T,
+    input: &I,
+    base_time: &Duration,
+) -> (Duration, u128, Duration) {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    print!(" > {}benching{}", ansi(ANSI_ITALIC), ansi(ANSI_RESET));
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench = &config::get().bench;
+    let bench_iterations = (bench.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(bench.min_samples.into(), bench.max_samples.into());
 
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
+        memo::clear_all();
         let timer = Instant::now();
-        func(cloned);
+        // the input is borrowed, samples only measure the solution itself.
+        black_box(func(black_box(input)));
         timers.push(timer.elapsed());
     }
 
+    let average = average_duration(&timers);
     (
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        Duration::from_nanos(average as u64),
         bench_iterations,
+        standard_deviation(&timers, average),
     )
 }
 
+/// Returns the number of fuzzing iterations if the solution runs with `--fuzz [iterations]`.
+pub fn fuzz_iterations() -> Option<usize> {
+    args()
+        .fuzz
+        .map(|iterations| iterations.unwrap_or(fuzz::DEFAULT_ITERATIONS))
+}
+
+/// Fuzzes both parts, prints a report and exits. The exit code tells whether a part panicked
+/// ([`fuzz::EXIT_PANIC`]) or hung ([`fuzz::EXIT_HANG`]).
+pub fn fuzz_parts<A: 'static, B: 'static>(
+    day: Day,
+    part_one: fn(&str) -> A,
+    part_two: fn(&str) -> B,
+    iterations: usize,
+) -> ! {
+    let corpus = fuzz::corpus(day);
+    let outcomes = [
+        fuzz::fuzz_part(part_one, &corpus, iterations),
+        fuzz::fuzz_part(part_two, &corpus, iterations),
+    ];
+
+    let mut exit_code = 0;
+    for (index, outcome) in outcomes.iter().enumerate() {
+        let part = index as u8 + 1;
+        println!("Part {part}: {outcome}");
+        let (fuzz::Outcome::Panic { input, .. } | fuzz::Outcome::Hang { input }) = outcome else {
+            continue;
+        };
+        match fuzz::save_failure(day, part, input) {
+            Ok(path) => println!("        failing input saved to \"{path}\""),
+            Err(e) => eprintln!("        could not save failing input: {e}"),
+        }
+        exit_code = match outcome {
+            fuzz::Outcome::Hang { .. } => exit_code.max(fuzz::EXIT_HANG),
+            _ => exit_code.max(fuzz::EXIT_PANIC),
+        };
+    }
+
+    process::exit(exit_code);
+}
+
+/// Arguments of the solution binary, see [`Solution`]. Prints the help or the error and exits if
+/// they are invalid.
+fn args() -> &'static Solution {
+    static ARGS: OnceLock<Solution> = OnceLock::new();
+    ARGS.get_or_init(Solution::parse)
+}
+
+fn flags() -> &'static SolutionFlags {
+    &args().flags
+}
+
+/// Plays back or writes the frames recorded by a part, as requested with `--record <play|gif|frames>`.
+fn output_recording(day: Day, part: u8) {
+    let Some(output) = flags().record else {
+        return;
+    };
+
+    let frames = recorder::take_frames();
+    if frames.is_empty() {
+        println!("No frames recorded.");
+        return;
+    }
+
+    let fps = flags().fps.unwrap_or(10);
+    let name = format!("part{part}");
+    let result = match output {
+        recorder::Output::Play => recorder::play(&frames, fps).map(|()| None),
+        recorder::Output::Gif => recorder::write_gif(&frames, fps, day, &name).map(Some),
+        recorder::Output::Frames => recorder::write_frames(&frames, day, &name).map(Some),
+    };
+
+    match result {
+        Ok(Some(path)) => println!("Wrote {} frames to {}", frames.len(), path.display()),
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to output recording: {e}"),
+    }
+}
+
+/// Prints and writes the spans recorded by a part, as requested with `--profile`, or only prints
+/// the duration of its `parse` span, as requested with `--parse-time`.
+fn output_profile(day: Day, part: u8) {
+    if !flags().profile && !args().parse_time {
+        return;
+    }
+
+    let report = profile::take_report();
+    if args().parse_time {
+        if let Some(parse) = report.as_ref().and_then(|report| report.span("parse")) {
+            println!("        parse: {parse:.1?}");
+        }
+    }
+    if !flags().profile {
+        return;
+    }
+    let Some(report) = report else {
+        println!("No spans recorded.");
+        return;
+    };
+
+    let root = format!("part{part}");
+    report.print(&root);
+    match report.write_folded(day, &root) {
+        Ok(path) => println!("Wrote folded stacks to {}", path.display()),
+        Err(e) => eprintln!("Failed to write folded stacks: {e}"),
+    }
+}
+
 fn average_duration(numbers: &[Duration]) -> u128 {
     numbers
         .iter()
@@ -84,6 +262,15 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+fn standard_deviation(numbers: &[Duration], average: u128) -> Duration {
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - average as f64).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+    Duration::from_secs_f64(variance.sqrt() / 1e9)
+}
+
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -92,13 +279,36 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Formats a measurement, e.g. ` (5.9µs ±0.3µs @ 7558 samples) [12 allocs, 1.2 KiB peak]`.
+fn format_measurement(measurement: &Measurement) -> String {
+    let mut out = match measurement.stddev {
+        Some(stddev) if measurement.samples > 1 => format!(
+            " ({:.1?} ±{stddev:.1?} @ {} samples)",
+            measurement.duration, measurement.samples
+        ),
+        _ => format_duration(&measurement.duration, measurement.samples),
+    };
+    if let Some(allocations) = measurement.allocations {
+        out.push_str(&format!(" [{allocations}]"));
+    }
+    out
+}
+
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            if result.is_multiline() {
+                // pictures of letters that could be read are printed as text above the picture.
+                let str = match result.text() {
+                    Some(text) => format!(
+                        "{part}: {}{text}{}{duration_str}",
+                        ansi(ANSI_BOLD),
+                        ansi(ANSI_RESET)
+                    ),
+                    None => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -107,7 +317,8 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+                let str = format!("{part}: {bold}{result}{reset}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -127,41 +338,74 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+/// Warns if the result is a value that did not fit into the type it was narrowed to with
+/// [`overflow::narrow`], checked in debug builds.
+fn check_truncation(result: &Answer, truncations: &[overflow::Truncation]) {
+    let Answer::Integer(result) = result else {
+        return;
+    };
+    if let Some(truncation) = truncations.iter().find(|t| t.narrow == *result) {
+        println!(
+            "        ⚠ the result was truncated from {}{}{}, return a wider type like u64",
+            ansi(ANSI_BOLD),
+            truncation.wide,
+            ansi(ANSI_RESET)
+        );
     }
+}
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
-
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+/// Warns if the result differs from the answer recorded in `data/answers/`.
+fn verify_result(result: &Answer, day: Day, part: u8) {
+    let (Ok(answers), Some(result)) = (answers::read(day), result.text()) else {
+        return;
     };
+    if let answers::Verdict::Wrong(answer) = answers::verify(&answers, part, &result) {
+        println!(
+            "        ✖ expected {}{answer}{}, as recorded in \"{}\"",
+            ansi(ANSI_BOLD),
+            ansi(ANSI_RESET),
+            answers::get_path(day).display()
+        );
+    }
+}
 
-    if part_submit != part {
+/// Try to submit one part of the solution if it was requested with `--submit <part>` and
+/// aoc-cli is installed.
+fn submit_result(
+    result: Answer,
+    day: Day,
+    part: u8,
+) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    if flags().submit_part != Some(part) {
         return None;
     }
 
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+        process::exit(cli::EXIT_FAILURE);
     }
 
+    let Some(result) = result.text() else {
+        eprintln!("The result cannot be submitted, its letters could not be read.");
+        return None;
+    };
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &result))
+}
+
+/// Records whether a submitted answer was accepted, for the `stats` command.
+fn record_submission(output: &Output, day: Day, part: u8, runtime: Duration) {
+    let outcome = stats::Outcome::from_response(&String::from_utf8_lossy(&output.stdout));
+    let event = stats::Event::now(
+        day,
+        stats::Kind::Submit {
+            part,
+            outcome,
+            runtime: Some(runtime),
+        },
+    );
+    if let Err(e) = stats::record(event) {
+        eprintln!("Failed to record submission: {e}");
+    }
 }
@@ -0,0 +1,319 @@
+/// Personal statistics, kept in `data/stats.jsonl`.
+///
+/// Scaffolding, downloading and submitting append an event with a timestamp to the file, one JSON
+/// object per line. The `stats` command combines them into the time it took to solve each part,
+/// the number of wrong answers, and the runtime of the solution that got the star.
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{ansi, calendar, config, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+/// Result of a submission, as reported by the website.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Correct,
+    Wrong,
+    /// Rate limited or already solved, the answer was not checked.
+    Unchecked,
+}
+
+impl Outcome {
+    /// Classifies the text of the response to a submission.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Outcome::Correct
+        } else if response.contains("That's not the right answer") {
+            Outcome::Wrong
+        } else {
+            Outcome::Unchecked
+        }
+    }
+}
+
+/// What happened, stored in the `event` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum Kind {
+    Scaffold,
+    Download,
+    Submit {
+        part: u8,
+        outcome: Outcome,
+        /// Duration of the solution run that produced the answer.
+        #[serde(rename = "runtime_nanos", with = "nanos")]
+        runtime: Option<Duration>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    pub year: u16,
+    pub day: Day,
+    /// Unix timestamp.
+    pub at: i64,
+    #[serde(flatten)]
+    pub kind: Kind,
+}
+
+/// Reads and writes an optional [`Duration`] as a number of nanoseconds.
+mod nanos {
+    use std::time::Duration;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_u64(duration.as_nanos() as u64),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<u64>::deserialize(deserializer).map(|nanos| nanos.map(Duration::from_nanos))
+    }
+}
+
+impl Event {
+    /// Creates an event that happens now, in the configured year or the current event.
+    pub fn now(day: Day, kind: Kind) -> Self {
+        let at = calendar::now();
+        Self {
+            year: config::get()
+                .year
+                .unwrap_or_else(|| calendar::event_year(at)),
+            day,
+            at,
+            kind,
+        }
+    }
+}
+
+pub fn get_path() -> PathBuf {
+    config::get().paths.data.join("stats.jsonl")
+}
+
+/// Appends an event to `data/stats.jsonl`.
+pub fn record(event: Event) -> io::Result<()> {
+    let path = get_path();
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let line = serde_json::to_string(&event).map_err(io::Error::other)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all recorded events, skipping lines that cannot be parsed.
+pub fn read() -> io::Result<Vec<Event>> {
+    match fs::read_to_string(get_path()) {
+        Ok(content) => Ok(parse(&content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse(content: &str) -> Vec<Event> {
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Statistics of a day, combined from its events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayStats {
+    /// When the day was first scaffolded or downloaded.
+    pub started: Option<i64>,
+    /// When each part was first accepted.
+    pub solved: [Option<i64>; 2],
+    pub wrong_answers: [usize; 2],
+    /// Runtime of the solution whose answer was accepted, per part.
+    pub runtime: [Option<Duration>; 2],
+}
+
+impl DayStats {
+    /// Time from starting the day to the star of part one.
+    pub fn time_to_part_1(&self) -> Option<i64> {
+        Some(self.solved[0]? - self.started?)
+    }
+
+    /// Time between the stars of both parts.
+    pub fn split(&self) -> Option<i64> {
+        Some(self.solved[1]? - self.solved[0]?)
+    }
+}
+
+/// Combines events into statistics per year and day.
+pub fn summarize(events: &[Event]) -> BTreeMap<(u16, Day), DayStats> {
+    let mut events = events.to_vec();
+    events.sort_by_key(|e| e.at);
+
+    let mut stats: BTreeMap<(u16, Day), DayStats> = BTreeMap::new();
+    for event in events {
+        let day = stats.entry((event.year, event.day)).or_default();
+        match event.kind {
+            Kind::Scaffold | Kind::Download => {
+                day.started.get_or_insert(event.at);
+            }
+            Kind::Submit {
+                part,
+                outcome,
+                runtime,
+            } => {
+                let index = usize::from(part.clamp(1, 2) - 1);
+                // submissions after the star do not count.
+                if day.solved[index].is_some() {
+                    continue;
+                }
+                match outcome {
+                    Outcome::Correct => {
+                        day.solved[index] = Some(event.at);
+                        day.runtime[index] = runtime;
+                    }
+                    Outcome::Wrong => day.wrong_answers[index] += 1,
+                    Outcome::Unchecked => {}
+                }
+            }
+        }
+    }
+    stats
+}
+
+/// Renders statistics as a table, one row per day, optionally only for one year.
+pub fn render(stats: &BTreeMap<(u16, Day), DayStats>, year: Option<u16>) -> String {
+    let time = |seconds: Option<i64>| seconds.map_or("-".into(), calendar::format_duration);
+    let runtime = |runtime: Option<Duration>| runtime.map_or("-".into(), |d| format!("{d:.1?}"));
+
+    let (bold, reset) = (ansi(ANSI_BOLD), ansi(ANSI_RESET));
+    let mut out = format!(
+        "{bold}{:<4} {:>3} {:>14} {:>14} {:>7} {:>12} {:>12}{reset}\n",
+        "Year", "Day", "Part 1", "Part 2 split", "Wrong", "Runtime 1", "Runtime 2"
+    );
+    for ((stats_year, day), stats) in stats {
+        if year.is_some_and(|year| year != *stats_year) {
+            continue;
+        }
+        out.push_str(&format!(
+            "{stats_year:<4} {:>3} {:>14} {:>14} {:>7} {:>12} {:>12}\n",
+            day.into_inner(),
+            time(stats.time_to_part_1()),
+            time(stats.split()),
+            format!("{} / {}", stats.wrong_answers[0], stats.wrong_answers[1]),
+            runtime(stats.runtime[0]),
+            runtime(stats.runtime[1]),
+        ));
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse, render, summarize, Event, Kind, Outcome};
+    use crate::day;
+
+    fn submit(at: i64, part: u8, outcome: Outcome) -> Event {
+        Event {
+            year: 2023,
+            day: day!(1),
+            at,
+            kind: Kind::Submit {
+                part,
+                outcome,
+                runtime: Some(Duration::from_micros(at as u64)),
+            },
+        }
+    }
+
+    fn get_mock_events() -> Vec<Event> {
+        vec![
+            Event {
+                year: 2023,
+                day: day!(1),
+                at: 100,
+                kind: Kind::Scaffold,
+            },
+            Event {
+                year: 2023,
+                day: day!(1),
+                at: 120,
+                kind: Kind::Download,
+            },
+            submit(400, 1, Outcome::Wrong),
+            submit(460, 1, Outcome::Unchecked),
+            submit(500, 1, Outcome::Correct),
+            submit(600, 2, Outcome::Wrong),
+            submit(700, 2, Outcome::Wrong),
+            submit(900, 2, Outcome::Correct),
+            submit(950, 2, Outcome::Wrong),
+        ]
+    }
+
+    #[test]
+    fn classifies_responses() {
+        assert_eq!(
+            Outcome::from_response("That's the right answer! You are one gold star closer."),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::from_response("That's not the right answer; your answer is too low."),
+            Outcome::Wrong
+        );
+        assert_eq!(
+            Outcome::from_response("You gave an answer too recently"),
+            Outcome::Unchecked
+        );
+    }
+
+    #[test]
+    fn reads_what_it_writes() {
+        let events = get_mock_events();
+        let content: String = events
+            .iter()
+            .map(|e| format!("{}\n", serde_json::to_string(e).unwrap()))
+            .collect();
+        assert_eq!(parse(&(content + "not json\n")), events);
+    }
+
+    #[test]
+    fn summarizes_events() {
+        let stats = summarize(&get_mock_events());
+        let day = &stats[&(2023, day!(1))];
+        assert_eq!(day.time_to_part_1(), Some(400));
+        assert_eq!(day.split(), Some(400));
+        assert_eq!(day.wrong_answers, [1, 2]);
+        assert_eq!(day.runtime[1], Some(Duration::from_micros(900)));
+    }
+
+    #[test]
+    fn renders_stats() {
+        let out = render(&summarize(&get_mock_events()), None);
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[1],
+            "2023   1       00:06:40       00:06:40   1 / 2      500.0µs      900.0µs"
+        );
+        assert_eq!(
+            render(&summarize(&get_mock_events()), Some(2022))
+                .lines()
+                .count(),
+            1
+        );
+    }
+}
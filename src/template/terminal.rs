@@ -0,0 +1,154 @@
+/// Full-screen terminal output and key input for interactive commands.
+///
+/// The terminal is switched to raw mode with `stty`, so keys are read from stdin as they are
+/// pressed, with reads that time out after a tenth of a second to tell the escape key from the
+/// escape sequences of arrow keys. The screen is drawn with ANSI escape codes on the alternate
+/// screen, which keeps the scrollback of the shell intact. `stty` only exists on Unix, so this
+/// module is not built on other platforms.
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+
+const ENTER_SCREEN: &str = "\x1b[?1049h\x1b[?25l";
+const LEAVE_SCREEN: &str = "\x1b[?25h\x1b[?1049l";
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+/// A key read from the terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Escape,
+    /// Ctrl-C, which does not interrupt the process in raw mode.
+    Interrupt,
+    Char(char),
+    Other,
+}
+
+/// Decodes the bytes a key sends, e.g. `ESC [ A` for the up arrow.
+fn decode(bytes: &[u8]) -> Key {
+    match bytes {
+        [0x1b, b'[' | b'O', b'A'] => Key::Up,
+        [0x1b, b'[' | b'O', b'B'] => Key::Down,
+        [0x1b, b'[' | b'O', b'C'] => Key::Right,
+        [0x1b, b'[' | b'O', b'D'] => Key::Left,
+        [b'\r' | b'\n'] => Key::Enter,
+        [0x1b] => Key::Escape,
+        [0x03] => Key::Interrupt,
+        [c] if c.is_ascii() && !c.is_ascii_control() => Key::Char(char::from(*c)),
+        _ => Key::Other,
+    }
+}
+
+/// Runs `stty` on the terminal of stdin and returns what it printed.
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(io::Error::other(message));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The terminal in raw mode on the alternate screen. The previous mode is restored on drop.
+pub struct Screen {
+    /// Settings of the terminal before raw mode, as printed by `stty -g`.
+    saved: String,
+}
+
+impl Screen {
+    /// Switches the terminal to raw mode and to the alternate screen.
+    pub fn enter() -> io::Result<Self> {
+        let screen = Self {
+            saved: stty(&["-g"])?,
+        };
+        screen.resume()?;
+        Ok(screen)
+    }
+
+    fn resume(&self) -> io::Result<()> {
+        // reads return after a tenth of a second, even without input.
+        stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+        let mut stdout = io::stdout();
+        write!(stdout, "{ENTER_SCREEN}")?;
+        stdout.flush()
+    }
+
+    fn leave(&self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        write!(stdout, "{LEAVE_SCREEN}")?;
+        stdout.flush()?;
+        stty(&[&self.saved]).map(|_| ())
+    }
+
+    /// Replaces the content of the screen with `lines`.
+    pub fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        // raw mode does not return the cursor to the start of the line on a line feed.
+        write!(stdout, "{CLEAR}{}", lines.join("\r\n"))?;
+        stdout.flush()
+    }
+
+    /// Waits for a key press.
+    pub fn read_key(&mut self) -> io::Result<Key> {
+        let mut stdin = io::stdin().lock();
+        let mut bytes = [0; 3];
+        while stdin.read(&mut bytes[..1])? == 0 {}
+        // escape sequences of arrow keys arrive at once, a read that times out after an escape
+        // means the escape key itself was pressed.
+        let mut len = 1;
+        if bytes[0] == 0x1b {
+            while len < bytes.len() {
+                match stdin.read(&mut bytes[len..])? {
+                    0 => break,
+                    read => len += read,
+                }
+            }
+        }
+        Ok(decode(&bytes[..len]))
+    }
+
+    /// Restores the terminal to run `f` with the usual output, e.g. a command, then waits for a
+    /// key before it returns to the screen.
+    pub fn suspend<T>(&mut self, f: impl FnOnce() -> T) -> io::Result<T> {
+        self.leave()?;
+        let result = f();
+        print!("\nPress any key to return.");
+        io::stdout().flush()?;
+        self.resume()?;
+        self.read_key()?;
+        Ok(result)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = self.leave();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode, Key};
+
+    #[test]
+    fn decodes_keys() {
+        assert_eq!(decode(b"\x1b[A"), Key::Up);
+        assert_eq!(decode(b"\x1bOB"), Key::Down);
+        assert_eq!(decode(b"\x1b[C"), Key::Right);
+        assert_eq!(decode(b"\x1b[D"), Key::Left);
+        assert_eq!(decode(b"\r"), Key::Enter);
+        assert_eq!(decode(b"\x03"), Key::Interrupt);
+        assert_eq!(decode(b"\x1b"), Key::Escape);
+        assert_eq!(decode(b"q"), Key::Char('q'));
+        assert_eq!(decode(b"\x1b[Z"), Key::Other);
+        assert_eq!(decode(b"\x7f"), Key::Other);
+    }
+}
@@ -0,0 +1,351 @@
+/// Support for property-based and differential tests of solutions.
+///
+/// Only compiled with the `test_support` feature, which the crate enables for its own tests
+/// through a dev-dependency on itself.
+///
+/// Random cases come from a seeded [`Rng`] so that failures are reproducible: the seed defaults to
+/// a fixed value and can be changed with the `AOC_TEST_SEED` environment variable. When a case
+/// fails, it is shrunk to a minimal failing case through the [`Shrink`] trait before being
+/// reported.
+use std::env;
+use std::fmt::Debug;
+
+use crate::grid::Grid;
+pub use crate::rng::Rng;
+
+const DEFAULT_SEED: u64 = 0x2023_1201;
+
+/// Creates a generator seeded from `AOC_TEST_SEED`, or from a fixed default.
+pub fn rng_from_env() -> Rng {
+    Rng::new(seed_from_env())
+}
+
+fn seed_from_env() -> u64 {
+    env::var("AOC_TEST_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or(DEFAULT_SEED)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Generators for the input shapes that come up in most puzzles.
+pub mod gen {
+    use super::Rng;
+    use crate::grid::Grid;
+
+    /// `count` numbers in `min..=max`.
+    pub fn numbers(rng: &mut Rng, count: usize, min: u64, max: u64) -> Vec<u64> {
+        (0..count).map(|_| rng.range(min, max)).collect()
+    }
+
+    /// Rows of whitespace-separated signed numbers, e.g. sequences or histories.
+    pub fn number_rows(
+        rng: &mut Rng,
+        rows: usize,
+        columns: usize,
+        min: i64,
+        max: i64,
+    ) -> Vec<Vec<i64>> {
+        (0..rows)
+            .map(|_| (0..columns).map(|_| rng.range_i64(min, max)).collect())
+            .collect()
+    }
+
+    /// A grid filled with characters picked from `alphabet`.
+    pub fn grid(rng: &mut Rng, width: usize, height: usize, alphabet: &[char]) -> Grid<char> {
+        let cells = (0..width * height).map(|_| *rng.choose(alphabet)).collect();
+        Grid::from_cells(width, height, cells).expect("cell count matches the dimensions")
+    }
+
+    /// A word of `len` characters picked from `alphabet`, e.g. a node label.
+    pub fn word(rng: &mut Rng, len: usize, alphabet: &[char]) -> String {
+        (0..len).map(|_| *rng.choose(alphabet)).collect()
+    }
+
+    /// Arbitrary bytes, lossily converted to text. Useful to check parsers do not panic.
+    pub fn noise(rng: &mut Rng, max_len: usize) -> String {
+        let len = rng.range(0, max_len as u64) as usize;
+        let bytes: Vec<u8> = (0..len).map(|_| rng.next_u64() as u8).collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Values that can produce simpler candidates of themselves, used to minimise failing cases.
+pub trait Shrink: Sized {
+    /// Returns candidates strictly simpler than `self`, simplest first.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! impl_shrink_unsigned {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![];
+                if *self > 0 {
+                    candidates.push(0);
+                }
+                // x / 2, then ever closer to x: x - x / 4, x - x / 8, ..., x - 1.
+                let mut delta = *self / 2;
+                while delta > 0 {
+                    candidates.push(*self - delta);
+                    delta /= 2;
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_shrink_signed {
+    ($($t:ty),*) => {$(
+        impl Shrink for $t {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![];
+                if *self != 0 {
+                    candidates.push(0);
+                }
+                if *self < 0 && *self != <$t>::MIN {
+                    candidates.push(-*self);
+                }
+                let mut delta = *self / 2;
+                while delta != 0 {
+                    candidates.push(*self - delta);
+                    delta /= 2;
+                }
+                candidates
+            }
+        }
+    )*};
+}
+
+impl_shrink_unsigned!(u8, u16, u32, u64, usize);
+impl_shrink_signed!(i8, i16, i32, i64);
+
+impl Shrink for char {
+    fn shrink(&self) -> Vec<Self> {
+        ['a', '.', ' '].into_iter().filter(|c| c < self).collect()
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+        if !self.is_empty() {
+            candidates.push(vec![]);
+        }
+        // drop halves, then single elements, then shrink elements in place.
+        if self.len() > 1 {
+            let half = self.len() / 2;
+            candidates.push(self[half..].to_vec());
+            candidates.push(self[..half].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut without = self.clone();
+            without.remove(i);
+            candidates.push(without);
+        }
+        for (i, item) in self.iter().enumerate() {
+            for shrunk in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[i] = shrunk;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+impl<T: Shrink + Clone> Shrink for Grid<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = vec![];
+        let rows: Vec<Vec<T>> = self.rows().map(<[T]>::to_vec).collect();
+        // drop the last row or column, then shrink cells in place.
+        if self.height() > 1 {
+            let cells = rows[..rows.len() - 1].concat();
+            candidates.extend(Grid::from_cells(self.width(), self.height() - 1, cells));
+        }
+        if self.width() > 1 {
+            let cells = rows
+                .iter()
+                .flat_map(|row| row[..row.len() - 1].to_vec())
+                .collect();
+            candidates.extend(Grid::from_cells(self.width() - 1, self.height(), cells));
+        }
+        for ((x, y), cell) in self.iter() {
+            for shrunk in cell.shrink() {
+                let mut candidate = self.clone();
+                candidate[(x, y)] = shrunk;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+macro_rules! impl_shrink_tuple {
+    ($($name:ident: $index:tt),*) => {
+        impl<$($name: Shrink + Clone),*> Shrink for ($($name,)*) {
+            fn shrink(&self) -> Vec<Self> {
+                let mut candidates = vec![];
+                $(
+                    for shrunk in self.$index.shrink() {
+                        let mut candidate = self.clone();
+                        candidate.$index = shrunk;
+                        candidates.push(candidate);
+                    }
+                )*
+                candidates
+            }
+        }
+    };
+}
+
+impl_shrink_tuple!(A: 0, B: 1);
+impl_shrink_tuple!(A: 0, B: 1, C: 2);
+impl_shrink_tuple!(A: 0, B: 1, C: 2, D: 3);
+
+/// Greedily shrinks `value` as long as `fails` keeps holding for a simpler candidate.
+pub fn shrink<T: Shrink>(mut value: T, fails: impl Fn(&T) -> bool) -> T {
+    // bounded to keep pathological cases from running forever.
+    for _ in 0..10_000 {
+        match value
+            .shrink()
+            .into_iter()
+            .find(|candidate| fails(candidate))
+        {
+            Some(simpler) => value = simpler,
+            None => break,
+        }
+    }
+    value
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Checks that `fast` and `naive` agree on `cases` generated values. On disagreement, the case is
+/// shrunk and the test panics with the minimal case rendered through `render`.
+pub fn check_differential<T, R>(
+    cases: usize,
+    generate: impl Fn(&mut Rng) -> T,
+    render: impl Fn(&T) -> String,
+    fast: impl Fn(&T) -> R,
+    naive: impl Fn(&T) -> R,
+) where
+    T: Shrink + Clone,
+    R: PartialEq + Debug,
+{
+    let seed = seed_from_env();
+    let mut rng = Rng::new(seed);
+    let differs = |value: &T| fast(value) != naive(value);
+
+    for case in 0..cases {
+        let value = generate(&mut rng);
+        if differs(&value) {
+            let minimal = shrink(value, differs);
+            panic!(
+                "fast and naive implementations differ (case {case}, AOC_TEST_SEED={seed})\n\
+                 --- minimal input ---\n{}\n---------------------\n\
+                 fast:  {:?}\nnaive: {:?}",
+                render(&minimal),
+                fast(&minimal),
+                naive(&minimal)
+            );
+        }
+    }
+}
+
+/// Declares a test checking an optimised implementation against a naive reference on random
+/// inputs.
+///
+/// ```ignore
+/// advent_of_code::differential! {
+///     fn counting_matches_brute_force(cases = 500) {
+///         generate: |rng| (rng.range(0, 100), rng.range(0, 2500)),
+///         render: |(time, record)| format!("Time: {time}\nDistance: {record}"),
+///         fast: |&(time, record)| count_fast(time, record),
+///         naive: |&(time, record)| count_naive(time, record),
+///     }
+/// }
+/// ```
+///
+/// `render` is optional and defaults to the `Debug` output of the case.
+#[macro_export]
+macro_rules! differential {
+    (
+        fn $name:ident(cases = $cases:expr) {
+            generate: $generate:expr,
+            $(render: $render:expr,)?
+            fast: $fast:expr,
+            naive: $naive:expr $(,)?
+        }
+    ) => {
+        #[test]
+        fn $name() {
+            $crate::testing::check_differential(
+                $cases,
+                $generate,
+                $crate::__differential_render!($($render)?),
+                $fast,
+                $naive,
+            );
+        }
+    };
+}
+
+// Not part of the public API, picks the render function of `differential!`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __differential_render {
+    () => {
+        |value: &_| format!("{value:?}")
+    };
+    ($render:expr) => {
+        $render
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_differential, shrink, Rng};
+
+    #[test]
+    fn rng_is_deterministic() {
+        let a: Vec<_> = (0..5)
+            .map({
+                let mut rng = Rng::new(1);
+                move |_| rng.range(10, 20)
+            })
+            .collect();
+        let b: Vec<_> = (0..5)
+            .map({
+                let mut rng = Rng::new(1);
+                move |_| rng.range(10, 20)
+            })
+            .collect();
+        assert_eq!(a, b);
+        assert!(a.iter().all(|x| (10..=20).contains(x)));
+    }
+
+    #[test]
+    fn shrinks_to_minimal_case() {
+        let minimal = shrink(vec![7_u64, 120, 3, 55], |v| v.iter().any(|x| *x >= 50));
+        assert_eq!(minimal, vec![50]);
+    }
+
+    #[test]
+    #[should_panic(expected = "minimal input ---\n[10]")]
+    fn reports_minimal_differential_case() {
+        check_differential(
+            100,
+            |rng| vec![rng.range(0, 20), rng.range(0, 20)],
+            |v| format!("{v:?}"),
+            |v: &Vec<u64>| v.iter().filter(|x| **x < 10).count(),
+            |v: &Vec<u64>| v.iter().filter(|x| **x <= 10).count(),
+        );
+    }
+}
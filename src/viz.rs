@@ -0,0 +1,340 @@
+/// Visualisation helpers for debugging solutions.
+///
+/// Grids render either as coloured ANSI text or as PPM / PNG images, graphs export to Graphviz
+/// DOT. Output is only meant to be produced when the solution is run with `--viz`, see
+/// [`enabled`].
+use std::fmt::{Display, Write as _};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::grid::Grid;
+use crate::template::{config, ANSI_RESET};
+use crate::Day;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether visualisations were requested, i.e. the solution runs with `--viz` and is not being
+/// benchmarked.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+// Not part of the public API, toggled by the runner.
+#[doc(hidden)]
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns the path of a visualisation file, `data/viz/<day>-<name>.<extension>`, creating the
+/// folder if needed.
+pub fn output_path(day: Day, name: &str, extension: &str) -> io::Result<PathBuf> {
+    let folder = config::get().paths.data.join("viz");
+    fs::create_dir_all(&folder)?;
+    Ok(folder.join(format!("{day}-{name}.{extension}")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A 24-bit RGB colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GREY: Color = Color::rgb(96, 96, 96);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(64, 160, 43);
+    pub const YELLOW: Color = Color::rgb(223, 142, 29);
+    pub const BLUE: Color = Color::rgb(38, 139, 210);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// ANSI escape sequence setting this colour as the foreground (true colour).
+    pub fn ansi_foreground(self) -> String {
+        format!("\x1b[38;2;{};{};{}m", self.r, self.g, self.b)
+    }
+}
+
+/// Renders a grid as text, colouring the cells for which `highlight` returns a colour.
+pub fn render_ansi<T: Display>(
+    grid: &Grid<T>,
+    highlight: impl Fn((usize, usize), &T) -> Option<Color>,
+) -> String {
+    let mut out = String::new();
+    for ((x, y), cell) in grid.iter() {
+        if x == 0 && y > 0 {
+            out.push('\n');
+        }
+        match highlight((x, y), cell) {
+            Some(color) => {
+                let _ = write!(out, "{}{cell}{ANSI_RESET}", color.ansi_foreground());
+            }
+            None => {
+                let _ = write!(out, "{cell}");
+            }
+        }
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An RGB raster image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pixels: Grid<Color>,
+}
+
+impl Image {
+    /// Rasterises a grid, drawing every cell as a `scale` x `scale` square of the colour
+    /// returned by `color`.
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        scale: usize,
+        color: impl Fn((usize, usize), &T) -> Color,
+    ) -> Self {
+        let scale = scale.max(1);
+        let colors = grid.map(|position, cell| color(position, cell));
+        let mut pixels = Grid::new(grid.width() * scale, grid.height() * scale, Color::BLACK);
+        for ((x, y), color) in colors.iter() {
+            for dy in 0..scale {
+                for dx in 0..scale {
+                    pixels[(x * scale + dx, y * scale + dy)] = *color;
+                }
+            }
+        }
+        Self { pixels }
+    }
+
+    pub fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    pub fn pixels(&self) -> &Grid<Color> {
+        &self.pixels
+    }
+
+    /// Encodes the image as a binary PPM (P6) file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width(), self.height()).into_bytes();
+        for (_, color) in self.pixels.iter() {
+            out.extend([color.r, color.g, color.b]);
+        }
+        out
+    }
+
+    /// Encodes the image as an uncompressed PNG file.
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.height() * (1 + self.width() * 3));
+        for row in self.pixels.rows() {
+            // filter type "None" for every scanline.
+            raw.push(0);
+            for color in row {
+                raw.extend([color.r, color.g, color.b]);
+            }
+        }
+
+        let mut header = vec![];
+        header.extend((self.width() as u32).to_be_bytes());
+        header.extend((self.height() as u32).to_be_bytes());
+        // bit depth 8, colour type RGB, default compression, filter and interlace methods.
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        png::write_chunk(&mut out, b"IHDR", &header);
+        png::write_chunk(&mut out, b"IDAT", &png::zlib_stored(&raw));
+        png::write_chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    pub fn write_ppm(&self, path: impl AsRef<std::path::Path>) -> io::Result<()> {
+        fs::write(path, self.to_ppm())
+    }
+
+    pub fn write_png(&self, path: impl AsRef<std::path::Path>) -> io::Result<()> {
+        fs::write(path, self.to_png())
+    }
+}
+
+/// Minimal PNG container encoding: stored (uncompressed) deflate blocks are valid zlib data, which
+/// keeps the encoder small and dependency free.
+mod png {
+    pub fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc = !0_u32;
+        for &byte in bytes {
+            crc ^= u32::from(byte);
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 {
+                    0xedb8_8320 ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
+
+    fn adler32(bytes: &[u8]) -> u32 {
+        let (mut a, mut b) = (1_u32, 0_u32);
+        for &byte in bytes {
+            a = (a + u32::from(byte)) % 65521;
+            b = (b + a) % 65521;
+        }
+        (b << 16) | a
+    }
+
+    pub fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x78, 0x01];
+        let mut chunks = data.chunks(0xffff).peekable();
+        if chunks.peek().is_none() {
+            out.extend([1, 0, 0, 0xff, 0xff]);
+        }
+        while let Some(chunk) = chunks.next() {
+            let is_last = chunks.peek().is_none();
+            let len = chunk.len() as u16;
+            out.push(u8::from(is_last));
+            out.extend(len.to_le_bytes());
+            out.extend((!len).to_le_bytes());
+            out.extend(chunk);
+        }
+        out.extend(adler32(data).to_be_bytes());
+        out
+    }
+
+    pub fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        out.extend((data.len() as u32).to_be_bytes());
+        let start = out.len();
+        out.extend(kind);
+        out.extend(data);
+        let crc = crc32(&out[start..]);
+        out.extend(crc.to_be_bytes());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A directed graph description that displays as Graphviz DOT.
+///
+/// ```
+/// # use advent_of_code::viz::Dot;
+/// let mut dot = Dot::new("day08");
+/// dot.edge("AAA", "BBB", Some("L"));
+/// assert!(dot.to_string().contains("\"AAA\" -> \"BBB\" [label=\"L\"];"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Dot {
+    name: String,
+    nodes: Vec<(String, Option<Color>)>,
+    edges: Vec<(String, String, Option<String>)>,
+}
+
+impl Dot {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Declares a node, optionally filled with a colour. Nodes only referenced by edges do not
+    /// need to be declared.
+    pub fn node(&mut self, id: impl Display, fill: Option<Color>) -> &mut Self {
+        self.nodes.push((id.to_string(), fill));
+        self
+    }
+
+    pub fn edge(&mut self, from: impl Display, to: impl Display, label: Option<&str>) -> &mut Self {
+        self.edges
+            .push((from.to_string(), to.to_string(), label.map(Into::into)));
+        self
+    }
+
+    pub fn write(&self, path: impl AsRef<std::path::Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "digraph \"{}\" {{", escape(&self.name))?;
+        for (id, fill) in &self.nodes {
+            match fill {
+                Some(c) => writeln!(
+                    f,
+                    "    \"{}\" [style=filled, fillcolor=\"#{:02x}{:02x}{:02x}\"];",
+                    escape(id),
+                    c.r,
+                    c.g,
+                    c.b
+                )?,
+                None => writeln!(f, "    \"{}\";", escape(id))?,
+            }
+        }
+        for (from, to, label) in &self.edges {
+            write!(f, "    \"{}\" -> \"{}\"", escape(from), escape(to))?;
+            match label {
+                Some(label) => writeln!(f, " [label=\"{}\"];", escape(label))?,
+                None => writeln!(f, ";")?,
+            }
+        }
+        write!(f, "}}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{png, render_ansi, Color, Image};
+    use crate::grid::Grid;
+
+    #[test]
+    fn crc32_reference_value() {
+        assert_eq!(png::crc32(b"IEND"), 0xae42_6082);
+    }
+
+    #[test]
+    fn renders_highlights() {
+        let grid = Grid::parse("a.\n.b").unwrap();
+        let out = render_ansi(&grid, |_, c| (*c == 'b').then_some(Color::RED));
+        assert_eq!(out, "a.\n.\x1b[38;2;220;50;47mb\x1b[0m");
+    }
+
+    #[test]
+    fn encodes_images() {
+        let grid = Grid::parse("#.\n..").unwrap();
+        let image = Image::from_grid(&grid, 2, |_, c| {
+            if *c == '#' {
+                Color::WHITE
+            } else {
+                Color::BLACK
+            }
+        });
+        assert_eq!((image.width(), image.height()), (4, 4));
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 4 * 4 * 3);
+
+        let png = image.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+}